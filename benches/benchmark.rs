@@ -46,9 +46,10 @@ fn benchmark_command_script_parts(c: &mut Criterion) {
 
 /// Benchmark rule loading
 fn benchmark_rule_loading(c: &mut Criterion) {
+    let settings = Settings::default();
     c.bench_function("get_builtin_rules", |b| {
         b.iter(|| {
-            black_box(get_builtin_rules())
+            black_box(get_builtin_rules(&settings))
         })
     });
 }
@@ -56,7 +57,7 @@ fn benchmark_rule_loading(c: &mut Criterion) {
 /// Benchmark corrector with different commands
 fn benchmark_corrector(c: &mut Criterion) {
     let settings = Settings::default();
-    let rules = get_builtin_rules();
+    let rules = get_builtin_rules(&settings);
     let rule_refs: Vec<&dyn thefuck::Rule> = rules.iter().map(|r| r.as_ref()).collect();
 
     let mut group = c.benchmark_group("Corrector");
@@ -129,7 +130,7 @@ fn benchmark_full_flow(c: &mut Criterion) {
             let settings = Settings::default();
 
             // 2. Load rules
-            let rules = get_builtin_rules();
+            let rules = get_builtin_rules(&settings);
             let rule_refs: Vec<&dyn thefuck::Rule> = rules.iter().map(|r| r.as_ref()).collect();
 
             // 3. Create command (pre-computed output)
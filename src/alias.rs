@@ -0,0 +1,151 @@
+//! Loads the user's git and shell aliases.
+//!
+//! Used by [`crate::rules::git_alias`] to suggest a likely intended alias
+//! when a mistyped `git <alias>` invocation fails, the same way
+//! [`crate::rules::git_not_command`] falls back to
+//! [`crate::similarity`] over built-in subcommands.
+
+use crate::executor::create_command;
+use std::collections::HashMap;
+
+/// Loads git aliases by parsing `git config --get-regexp '^alias\.'`.
+///
+/// Returns an empty map if git isn't installed, there's no repo config,
+/// or no aliases are defined.
+pub fn load_git_aliases() -> HashMap<String, String> {
+    let output = create_command("git")
+        .args(["config", "--get-regexp", r"^alias\."])
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            parse_git_config_output(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => HashMap::new(),
+    }
+}
+
+/// Parses `git config --get-regexp` output (`alias.<name> <expansion>` per
+/// line) into an alias name -> expansion map.
+fn parse_git_config_output(text: &str) -> HashMap<String, String> {
+    let mut aliases = HashMap::new();
+
+    for line in text.lines() {
+        let mut fields = line.splitn(2, ' ');
+        let key = match fields.next() {
+            Some(key) => key,
+            None => continue,
+        };
+        let expansion = match fields.next() {
+            Some(expansion) => expansion.trim(),
+            None => continue,
+        };
+
+        if let Some(name) = key.strip_prefix("alias.") {
+            aliases.insert(name.to_string(), expansion.to_string());
+        }
+    }
+
+    aliases
+}
+
+/// Loads shell aliases by running the user's interactive shell's `alias`
+/// builtin, falling back to parsing `~/.bashrc` and `~/.zshrc` directly if
+/// the shell can't be run interactively (e.g. in a non-interactive test
+/// environment).
+pub fn load_shell_aliases() -> HashMap<String, String> {
+    let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+
+    if let Ok(output) = create_command(&shell).args(["-i", "-c", "alias"]).output() {
+        if output.status.success() {
+            let aliases = parse_shell_aliases(&String::from_utf8_lossy(&output.stdout));
+            if !aliases.is_empty() {
+                return aliases;
+            }
+        }
+    }
+
+    let mut aliases = HashMap::new();
+    if let Some(home) = dirs::home_dir() {
+        for rc_file in [".bashrc", ".zshrc"] {
+            if let Ok(contents) = std::fs::read_to_string(home.join(rc_file)) {
+                for (name, expansion) in parse_shell_aliases(&contents) {
+                    aliases.entry(name).or_insert(expansion);
+                }
+            }
+        }
+    }
+
+    aliases
+}
+
+/// Parses `alias name=value` lines, as produced by the `alias` builtin or
+/// found in shell rc files. Lines that aren't alias definitions are
+/// ignored.
+fn parse_shell_aliases(text: &str) -> HashMap<String, String> {
+    let mut aliases = HashMap::new();
+
+    for line in text.lines() {
+        let rest = match line.trim().strip_prefix("alias ") {
+            Some(rest) => rest,
+            None => continue,
+        };
+
+        let mut fields = rest.splitn(2, '=');
+        let name = match fields.next() {
+            Some(name) => name.trim(),
+            None => continue,
+        };
+        let value = match fields.next() {
+            Some(value) => value.trim().trim_matches(['\'', '"']),
+            None => continue,
+        };
+
+        if !name.is_empty() {
+            aliases.insert(name.to_string(), value.to_string());
+        }
+    }
+
+    aliases
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_git_config_output() {
+        let text = "alias.co checkout\nalias.lg !git log --oneline --graph\n";
+        let aliases = parse_git_config_output(text);
+
+        assert_eq!(aliases.get("co"), Some(&"checkout".to_string()));
+        assert_eq!(
+            aliases.get("lg"),
+            Some(&"!git log --oneline --graph".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_git_config_output_ignores_non_alias_keys() {
+        let text = "user.name Jane Doe\nalias.co checkout\n";
+        let aliases = parse_git_config_output(text);
+
+        assert_eq!(aliases.len(), 1);
+        assert!(aliases.contains_key("co"));
+    }
+
+    #[test]
+    fn test_parse_shell_aliases() {
+        let text = "alias gs='git status'\nalias ll=\"ls -la\"\nexport PATH=/usr/bin\n";
+        let aliases = parse_shell_aliases(text);
+
+        assert_eq!(aliases.get("gs"), Some(&"git status".to_string()));
+        assert_eq!(aliases.get("ll"), Some(&"ls -la".to_string()));
+        assert_eq!(aliases.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_shell_aliases_empty_input() {
+        assert!(parse_shell_aliases("").is_empty());
+    }
+}
@@ -1,9 +1,12 @@
 use crate::error::{Result, TheFuckError};
+use crate::similarity::SimilarityMetric;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::env;
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 
 /// Special constant indicating all rules are enabled.
 pub const ALL_ENABLED: &str = "ALL";
@@ -11,15 +14,82 @@ pub const ALL_ENABLED: &str = "ALL";
 /// Default priority for rules.
 pub const DEFAULT_PRIORITY: i32 = 1000;
 
+/// Which configuration layer most recently set a field's value, as recorded
+/// by [`Settings::load_with_provenance`]. Mirrors the layering story rhg's
+/// `ConfigSource` gives Mercurial: when a setting isn't what you expect,
+/// this says whether to go look at the system config, your own
+/// `settings.toml`, an env var, or a CLI flag.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    /// The compiled-in default - no later layer overrode it.
+    Default,
+    /// Set by a TOML config file at this path (system-wide or per-user).
+    ConfigFile(PathBuf),
+    /// Set by this `THEFUCK_*` environment variable.
+    Env(String),
+    /// Set by a command-line flag.
+    Cli,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::ConfigFile(path) => write!(f, "{}", path.display()),
+            Source::Env(var) => write!(f, "env:{}", var),
+            Source::Cli => write!(f, "cli"),
+        }
+    }
+}
+
+/// Per-field provenance for a [`Settings`] built by
+/// [`Settings::load_with_provenance`], keyed by field name as it appears in
+/// `settings.toml`. A field never overwritten by a later layer is absent
+/// and reported as [`Source::Default`] by [`Self::source`].
+#[derive(Debug, Clone, Default)]
+pub struct SettingsProvenance(HashMap<&'static str, Source>);
+
+impl SettingsProvenance {
+    /// Records that `field` was last set by `source`, overwriting whatever
+    /// this field's provenance previously was.
+    fn record(&mut self, field: &'static str, source: Source) {
+        self.0.insert(field, source);
+    }
+
+    /// Returns the source that last set `field`, or [`Source::Default`] if
+    /// no layer past the compiled-in default ever touched it.
+    pub fn source(&self, field: &str) -> Source {
+        self.0.get(field).cloned().unwrap_or(Source::Default)
+    }
+}
+
+/// Shared, lazily-populated cache of compiled [`gix::glob::Pattern`]s for
+/// `Settings::excluded_search_path_prefixes`. Wrapped in `Arc<OnceLock<_>>`
+/// so `Settings` stays cheaply `Clone` (every clone shares one cache
+/// instead of recompiling) without needing `gix::glob::Pattern` itself to
+/// implement `Clone`/`Debug`.
+#[derive(Clone, Default)]
+struct CompiledExclusions(Arc<OnceLock<Vec<gix::glob::Pattern>>>);
+
+impl fmt::Debug for CompiledExclusions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CompiledExclusions(..)")
+    }
+}
+
 /// Application settings.
 ///
 /// Settings can be loaded from:
 /// 1. Default values
-/// 2. Config file (~/.config/thefuck/settings.toml)
-/// 3. Environment variables (THEFUCK_*)
-/// 4. Command-line arguments
+/// 2. System-wide config file (/etc/thefuck/settings.toml)
+/// 3. Per-user config file (~/.config/thefuck/settings.toml)
+/// 4. Environment variables (THEFUCK_*)
+/// 5. Command-line arguments
 ///
-/// Later sources override earlier ones.
+/// Later sources override earlier ones. [`Settings::load_with_provenance`]
+/// records which of these layers last set each field, for callers (like
+/// `thefuck config --sources`) that need to explain a setting's value
+/// rather than just report it.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Settings {
@@ -68,8 +138,64 @@ pub struct Settings {
     /// Environment variables to set when running commands.
     pub env: HashMap<String, String>,
 
-    /// Path prefixes to exclude when searching for executables.
+    /// Gitignore-style glob patterns (see [`Self::is_path_excluded`]) for
+    /// paths to exclude when searching for executables, e.g.
+    /// `**/node_modules/**` or `*.cache`. A leading `!` negates a pattern;
+    /// later patterns win over earlier ones, just like `.gitignore`.
     pub excluded_search_path_prefixes: Vec<String>,
+
+    /// Lazily compiled form of `excluded_search_path_prefixes`, built once
+    /// on the first [`Self::is_path_excluded`] call and reused after -
+    /// compiling a gitignore pattern isn't free, and this runs once per
+    /// `PATH` entry during executable scanning. Not part of the on-disk
+    /// config; assumes `excluded_search_path_prefixes` doesn't change after
+    /// the cache is populated.
+    #[serde(skip)]
+    compiled_exclusions: CompiledExclusions,
+
+    /// External fuzzy-chooser command (e.g. `fzf`, `sk`, `peco`) used to
+    /// select a correction instead of the built-in arrow-key selector.
+    /// Falls back to the `THEFUCK_CHOOSER` environment variable, then to
+    /// `fzf`, when unset.
+    pub chooser: Option<String>,
+
+    /// Whether accepted corrections are remembered and used to bias
+    /// future ranking via frecency (see [`crate::learning`]).
+    pub learning_enabled: bool,
+
+    /// Maximum number of ranked corrections [`crate::executor::execute_with_fallback`]
+    /// will try before giving up. `1` disables fallback entirely.
+    pub max_fallback_attempts: usize,
+
+    /// Similarity backend used by [`crate::rules::no_command::NoCommandRule`]
+    /// to rank "did you mean" suggestions from installed executables.
+    pub similarity_metric: SimilarityMetric,
+
+    /// Maximum normalized distance (`0.0` = identical, `1.0` = completely
+    /// different) a candidate may have under `similarity_metric` and still
+    /// be suggested by [`crate::rules::no_command::NoCommandRule`].
+    pub similarity_threshold: f64,
+
+    /// Re-run commands under a pseudo-terminal instead of a plain pipe, so
+    /// tools that change behavior based on `isatty()` (colorized `git`,
+    /// `ls`, `grep`, `apt`, ...) produce the same output the user saw.
+    /// Unix only; ignored on Windows and falls back to the piped path if
+    /// no PTY can be allocated.
+    pub pty_output: bool,
+
+    /// Maximum CPU time (`RLIMIT_CPU`, in seconds) a re-run command may
+    /// consume. `None` leaves it uncapped. Unix only.
+    pub max_cpu_seconds: Option<u64>,
+
+    /// Maximum address space (`RLIMIT_AS`, in bytes) a re-run command may
+    /// map. `None` leaves it uncapped. Unix only.
+    pub max_memory_bytes: Option<u64>,
+
+    /// Maximum size (`RLIMIT_FSIZE`, in bytes) a re-run command may write
+    /// to a regular file. `None` leaves it uncapped. Unix only. Note this
+    /// bounds file writes, not pipe output - it won't cap a command that
+    /// floods its own stdout pipe.
+    pub max_output_bytes: Option<u64>,
 }
 
 impl Default for Settings {
@@ -102,26 +228,57 @@ impl Default for Settings {
             num_close_matches: 3,
             env: env_vars,
             excluded_search_path_prefixes: vec![],
+            compiled_exclusions: CompiledExclusions::default(),
+            chooser: None,
+            learning_enabled: true,
+            max_fallback_attempts: 3,
+            similarity_metric: SimilarityMetric::default(),
+            similarity_threshold: 0.4,
+            pty_output: false,
+            max_cpu_seconds: None,
+            max_memory_bytes: None,
+            max_output_bytes: None,
         }
     }
 }
 
 impl Settings {
-    /// Creates settings from defaults, config file, env vars, and CLI args.
+    /// Creates settings from defaults, config files, env vars, and CLI args.
+    ///
+    /// Equivalent to [`Self::load_with_provenance`] for callers that don't
+    /// need to know which layer set each field.
     pub fn load() -> Result<Self> {
+        Self::load_with_provenance().map(|(settings, _)| settings)
+    }
+
+    /// Creates settings the same way [`Self::load`] does, additionally
+    /// returning a [`SettingsProvenance`] recording which layer last set
+    /// each field. CLI args aren't merged here since they aren't known at
+    /// this point in `main` - call [`Self::merge_from_args`] afterwards
+    /// with the same provenance to fold in `Source::Cli` entries too.
+    pub fn load_with_provenance() -> Result<(Self, SettingsProvenance)> {
         let mut settings = Self::default();
+        let mut provenance = SettingsProvenance::default();
 
-        // Load from config file
+        // System-wide config file, merged first so it acts as a fleet-wide
+        // default that the per-user file below can still override.
+        if let Some(system_path) = Self::system_config_file_path() {
+            if system_path.exists() {
+                settings.merge_from_file(&system_path, &mut provenance)?;
+            }
+        }
+
+        // Per-user config file
         if let Some(config_path) = Self::config_file_path() {
             if config_path.exists() {
-                settings.merge_from_file(&config_path)?;
+                settings.merge_from_file(&config_path, &mut provenance)?;
             }
         }
 
-        // Load from environment variables
-        settings.merge_from_env();
+        // Environment variables
+        settings.merge_from_env(&mut provenance);
 
-        Ok(settings)
+        Ok((settings, provenance))
     }
 
     /// Returns the config directory path.
@@ -153,6 +310,18 @@ impl Settings {
         Self::config_dir().map(|p| p.join("settings.toml"))
     }
 
+    /// Returns the system-wide config file path, checked before the
+    /// per-user one so admins can ship fleet-wide defaults that a user's
+    /// own `settings.toml` can still override. `None` on platforms without
+    /// a conventional system config location.
+    pub fn system_config_file_path() -> Option<PathBuf> {
+        if cfg!(unix) {
+            Some(PathBuf::from("/etc/thefuck/settings.toml"))
+        } else {
+            None
+        }
+    }
+
     /// Returns the user rules directory path.
     pub fn user_rules_dir() -> Option<PathBuf> {
         Self::config_dir().map(|p| p.join("rules"))
@@ -179,6 +348,13 @@ impl Settings {
             fs::create_dir_all(&rules_dir)?;
         }
 
+        // Drop the JSON Schema next to settings.toml so editors with
+        // TOML/JSON-schema support (e.g. via Taplo's `#:schema` comment)
+        // can offer inline docs and completion. Always refreshed, since an
+        // older cached schema would silently drift from the real fields.
+        let schema_file = config_dir.join("schema.json");
+        fs::write(&schema_file, Self::json_schema())?;
+
         // Create default config file if it doesn't exist
         let config_file = config_dir.join("settings.toml");
         if !config_file.exists() {
@@ -191,7 +367,8 @@ impl Settings {
 
     /// Returns the default config file content.
     fn default_config_content() -> String {
-        r#"# thefuck-rs settings
+        r#"#:schema ./schema.json
+# thefuck-rs settings
 # See https://github.com/nvbn/thefuck#settings for more information.
 
 # Enabled rules. Use ["ALL"] to enable all rules.
@@ -229,84 +406,165 @@ impl Settings {
 
 # Number of close matches to suggest
 # num_close_matches = 3
+
+# External fuzzy-chooser command used to pick a correction, e.g. "fzf"
+# chooser = "fzf"
+
+# Remember accepted corrections and bias future ranking toward them
+# learning_enabled = true
+
+# Similarity backend used to rank "did you mean" executable suggestions:
+# "jaro_winkler", "levenshtein", or "damerau_levenshtein"
+# similarity_metric = "damerau_levenshtein"
+
+# Maximum normalized distance (0.0 = identical, 1.0 = unrelated) a
+# suggestion may have under similarity_metric
+# similarity_threshold = 0.4
+
+# Re-run commands under a pseudo-terminal so colorized/interactive output
+# matches what you originally saw (Unix only)
+# pty_output = false
+
+# Resource limits applied to re-run commands (Unix only, null = uncapped)
+# max_cpu_seconds = null
+# max_memory_bytes = null
+# max_output_bytes = null
 "#
         .to_string()
     }
 
-    /// Merges settings from a TOML config file.
-    fn merge_from_file(&mut self, path: &PathBuf) -> Result<()> {
+    /// Merges settings from a TOML config file, recording `path` as the
+    /// [`Source`] of every field it overwrites.
+    fn merge_from_file(&mut self, path: &PathBuf, provenance: &mut SettingsProvenance) -> Result<()> {
         let content = fs::read_to_string(path)?;
         let file_settings: SettingsPartial = toml::from_str(&content)?;
+        let source = Source::ConfigFile(path.clone());
 
         // Merge each field if present
         if let Some(rules) = file_settings.rules {
             self.rules = rules;
+            provenance.record("rules", source.clone());
         }
         if let Some(exclude_rules) = file_settings.exclude_rules {
             self.exclude_rules = exclude_rules;
+            provenance.record("exclude_rules", source.clone());
         }
         if let Some(wait_command) = file_settings.wait_command {
             self.wait_command = wait_command;
+            provenance.record("wait_command", source.clone());
         }
         if let Some(wait_slow_command) = file_settings.wait_slow_command {
             self.wait_slow_command = wait_slow_command;
+            provenance.record("wait_slow_command", source.clone());
         }
         if let Some(require_confirmation) = file_settings.require_confirmation {
             self.require_confirmation = require_confirmation;
+            provenance.record("require_confirmation", source.clone());
         }
         if let Some(no_colors) = file_settings.no_colors {
             self.no_colors = no_colors;
+            provenance.record("no_colors", source.clone());
         }
         if let Some(debug) = file_settings.debug {
             self.debug = debug;
+            provenance.record("debug", source.clone());
         }
         if let Some(priority) = file_settings.priority {
             self.priority = priority;
+            provenance.record("priority", source.clone());
         }
         if let Some(history_limit) = file_settings.history_limit {
             self.history_limit = history_limit;
+            provenance.record("history_limit", source.clone());
         }
         if let Some(alter_history) = file_settings.alter_history {
             self.alter_history = alter_history;
+            provenance.record("alter_history", source.clone());
         }
         if let Some(slow_commands) = file_settings.slow_commands {
             self.slow_commands = slow_commands;
+            provenance.record("slow_commands", source.clone());
         }
         if let Some(repeat) = file_settings.repeat {
             self.repeat = repeat;
+            provenance.record("repeat", source.clone());
         }
         if let Some(instant_mode) = file_settings.instant_mode {
             self.instant_mode = instant_mode;
+            provenance.record("instant_mode", source.clone());
         }
         if let Some(num_close_matches) = file_settings.num_close_matches {
             self.num_close_matches = num_close_matches;
+            provenance.record("num_close_matches", source.clone());
         }
         if let Some(env) = file_settings.env {
             self.env.extend(env);
+            provenance.record("env", source.clone());
         }
         if let Some(excluded_search_path_prefixes) = file_settings.excluded_search_path_prefixes {
             self.excluded_search_path_prefixes = excluded_search_path_prefixes;
+            provenance.record("excluded_search_path_prefixes", source.clone());
+        }
+        if let Some(chooser) = file_settings.chooser {
+            self.chooser = Some(chooser);
+            provenance.record("chooser", source.clone());
+        }
+        if let Some(learning_enabled) = file_settings.learning_enabled {
+            self.learning_enabled = learning_enabled;
+            provenance.record("learning_enabled", source.clone());
+        }
+        if let Some(max_fallback_attempts) = file_settings.max_fallback_attempts {
+            self.max_fallback_attempts = max_fallback_attempts;
+            provenance.record("max_fallback_attempts", source.clone());
+        }
+        if let Some(similarity_metric) = file_settings.similarity_metric {
+            self.similarity_metric = similarity_metric;
+            provenance.record("similarity_metric", source.clone());
+        }
+        if let Some(similarity_threshold) = file_settings.similarity_threshold {
+            self.similarity_threshold = similarity_threshold;
+            provenance.record("similarity_threshold", source.clone());
+        }
+        if let Some(pty_output) = file_settings.pty_output {
+            self.pty_output = pty_output;
+            provenance.record("pty_output", source.clone());
+        }
+        if let Some(max_cpu_seconds) = file_settings.max_cpu_seconds {
+            self.max_cpu_seconds = max_cpu_seconds;
+            provenance.record("max_cpu_seconds", source.clone());
+        }
+        if let Some(max_memory_bytes) = file_settings.max_memory_bytes {
+            self.max_memory_bytes = max_memory_bytes;
+            provenance.record("max_memory_bytes", source.clone());
+        }
+        if let Some(max_output_bytes) = file_settings.max_output_bytes {
+            self.max_output_bytes = max_output_bytes;
+            provenance.record("max_output_bytes", source.clone());
         }
 
         Ok(())
     }
 
-    /// Merges settings from environment variables.
-    fn merge_from_env(&mut self) {
+    /// Merges settings from environment variables, recording the variable
+    /// name as the [`Source`] of every field it overwrites.
+    fn merge_from_env(&mut self, provenance: &mut SettingsProvenance) {
         // THEFUCK_RULES - colon-separated list
         if let Ok(val) = env::var("THEFUCK_RULES") {
             self.rules = Self::parse_rules_env(&val);
+            provenance.record("rules", Source::Env("THEFUCK_RULES".to_string()));
         }
 
         // THEFUCK_EXCLUDE_RULES - colon-separated list
         if let Ok(val) = env::var("THEFUCK_EXCLUDE_RULES") {
             self.exclude_rules = val.split(':').map(String::from).collect();
+            provenance.record("exclude_rules", Source::Env("THEFUCK_EXCLUDE_RULES".to_string()));
         }
 
         // THEFUCK_WAIT_COMMAND
         if let Ok(val) = env::var("THEFUCK_WAIT_COMMAND") {
             if let Ok(n) = val.parse() {
                 self.wait_command = n;
+                provenance.record("wait_command", Source::Env("THEFUCK_WAIT_COMMAND".to_string()));
             }
         }
 
@@ -314,79 +572,175 @@ impl Settings {
         if let Ok(val) = env::var("THEFUCK_WAIT_SLOW_COMMAND") {
             if let Ok(n) = val.parse() {
                 self.wait_slow_command = n;
+                provenance.record("wait_slow_command", Source::Env("THEFUCK_WAIT_SLOW_COMMAND".to_string()));
             }
         }
 
         // THEFUCK_REQUIRE_CONFIRMATION
         if let Ok(val) = env::var("THEFUCK_REQUIRE_CONFIRMATION") {
             self.require_confirmation = val.eq_ignore_ascii_case("true");
+            provenance.record("require_confirmation", Source::Env("THEFUCK_REQUIRE_CONFIRMATION".to_string()));
         }
 
         // THEFUCK_NO_COLORS
         if let Ok(val) = env::var("THEFUCK_NO_COLORS") {
             self.no_colors = val.eq_ignore_ascii_case("true");
+            provenance.record("no_colors", Source::Env("THEFUCK_NO_COLORS".to_string()));
         }
 
         // THEFUCK_DEBUG
         if let Ok(val) = env::var("THEFUCK_DEBUG") {
             self.debug = val.eq_ignore_ascii_case("true");
+            provenance.record("debug", Source::Env("THEFUCK_DEBUG".to_string()));
         }
 
         // THEFUCK_PRIORITY - colon-separated rule=priority pairs
         if let Ok(val) = env::var("THEFUCK_PRIORITY") {
             self.priority = Self::parse_priority_env(&val);
+            provenance.record("priority", Source::Env("THEFUCK_PRIORITY".to_string()));
         }
 
         // THEFUCK_HISTORY_LIMIT
         if let Ok(val) = env::var("THEFUCK_HISTORY_LIMIT") {
             if let Ok(n) = val.parse() {
                 self.history_limit = Some(n);
+                provenance.record("history_limit", Source::Env("THEFUCK_HISTORY_LIMIT".to_string()));
             }
         }
 
         // THEFUCK_ALTER_HISTORY
         if let Ok(val) = env::var("THEFUCK_ALTER_HISTORY") {
             self.alter_history = val.eq_ignore_ascii_case("true");
+            provenance.record("alter_history", Source::Env("THEFUCK_ALTER_HISTORY".to_string()));
         }
 
         // THEFUCK_SLOW_COMMANDS - colon-separated list
         if let Ok(val) = env::var("THEFUCK_SLOW_COMMANDS") {
             self.slow_commands = val.split(':').map(String::from).collect();
+            provenance.record("slow_commands", Source::Env("THEFUCK_SLOW_COMMANDS".to_string()));
         }
 
         // THEFUCK_REPEAT
         if let Ok(val) = env::var("THEFUCK_REPEAT") {
             self.repeat = val.eq_ignore_ascii_case("true");
+            provenance.record("repeat", Source::Env("THEFUCK_REPEAT".to_string()));
         }
 
         // THEFUCK_INSTANT_MODE
         if let Ok(val) = env::var("THEFUCK_INSTANT_MODE") {
             self.instant_mode = val.eq_ignore_ascii_case("true");
+            provenance.record("instant_mode", Source::Env("THEFUCK_INSTANT_MODE".to_string()));
         }
 
         // THEFUCK_NUM_CLOSE_MATCHES
         if let Ok(val) = env::var("THEFUCK_NUM_CLOSE_MATCHES") {
             if let Ok(n) = val.parse() {
                 self.num_close_matches = n;
+                provenance.record("num_close_matches", Source::Env("THEFUCK_NUM_CLOSE_MATCHES".to_string()));
             }
         }
 
         // THEFUCK_EXCLUDED_SEARCH_PATH_PREFIXES - colon-separated list
         if let Ok(val) = env::var("THEFUCK_EXCLUDED_SEARCH_PATH_PREFIXES") {
             self.excluded_search_path_prefixes = val.split(':').map(String::from).collect();
+            provenance.record(
+                "excluded_search_path_prefixes",
+                Source::Env("THEFUCK_EXCLUDED_SEARCH_PATH_PREFIXES".to_string()),
+            );
+        }
+
+        // THEFUCK_CHOOSER
+        if let Ok(val) = env::var("THEFUCK_CHOOSER") {
+            self.chooser = Some(val);
+            provenance.record("chooser", Source::Env("THEFUCK_CHOOSER".to_string()));
+        }
+
+        // THEFUCK_LEARNING_ENABLED
+        if let Ok(val) = env::var("THEFUCK_LEARNING_ENABLED") {
+            self.learning_enabled = val.eq_ignore_ascii_case("true");
+            provenance.record("learning_enabled", Source::Env("THEFUCK_LEARNING_ENABLED".to_string()));
+        }
+
+        // THEFUCK_MAX_FALLBACK_ATTEMPTS
+        if let Ok(val) = env::var("THEFUCK_MAX_FALLBACK_ATTEMPTS") {
+            if let Ok(n) = val.parse() {
+                self.max_fallback_attempts = n;
+                provenance.record("max_fallback_attempts", Source::Env("THEFUCK_MAX_FALLBACK_ATTEMPTS".to_string()));
+            }
+        }
+
+        // THEFUCK_SIMILARITY_METRIC - "jaro_winkler" | "levenshtein" | "damerau_levenshtein"
+        if let Ok(val) = env::var("THEFUCK_SIMILARITY_METRIC") {
+            if let Some(metric) = Self::parse_similarity_metric(&val) {
+                self.similarity_metric = metric;
+                provenance.record("similarity_metric", Source::Env("THEFUCK_SIMILARITY_METRIC".to_string()));
+            }
+        }
+
+        // THEFUCK_SIMILARITY_THRESHOLD
+        if let Ok(val) = env::var("THEFUCK_SIMILARITY_THRESHOLD") {
+            if let Ok(n) = val.parse() {
+                self.similarity_threshold = n;
+                provenance.record("similarity_threshold", Source::Env("THEFUCK_SIMILARITY_THRESHOLD".to_string()));
+            }
+        }
+
+        // THEFUCK_PTY_OUTPUT
+        if let Ok(val) = env::var("THEFUCK_PTY_OUTPUT") {
+            self.pty_output = val.eq_ignore_ascii_case("true");
+            provenance.record("pty_output", Source::Env("THEFUCK_PTY_OUTPUT".to_string()));
+        }
+
+        // THEFUCK_MAX_CPU_SECONDS
+        if let Ok(val) = env::var("THEFUCK_MAX_CPU_SECONDS") {
+            if let Ok(n) = val.parse() {
+                self.max_cpu_seconds = Some(n);
+                provenance.record("max_cpu_seconds", Source::Env("THEFUCK_MAX_CPU_SECONDS".to_string()));
+            }
+        }
+
+        // THEFUCK_MAX_MEMORY_BYTES
+        if let Ok(val) = env::var("THEFUCK_MAX_MEMORY_BYTES") {
+            if let Ok(n) = val.parse() {
+                self.max_memory_bytes = Some(n);
+                provenance.record("max_memory_bytes", Source::Env("THEFUCK_MAX_MEMORY_BYTES".to_string()));
+            }
+        }
+
+        // THEFUCK_MAX_OUTPUT_BYTES
+        if let Ok(val) = env::var("THEFUCK_MAX_OUTPUT_BYTES") {
+            if let Ok(n) = val.parse() {
+                self.max_output_bytes = Some(n);
+                provenance.record("max_output_bytes", Source::Env("THEFUCK_MAX_OUTPUT_BYTES".to_string()));
+            }
+        }
+    }
+
+    /// Parses a similarity metric name from config/env, matching the
+    /// `serde(rename_all = "snake_case")` names used in `settings.toml`.
+    fn parse_similarity_metric(val: &str) -> Option<SimilarityMetric> {
+        match val {
+            "jaro_winkler" => Some(SimilarityMetric::JaroWinkler),
+            "levenshtein" => Some(SimilarityMetric::Levenshtein),
+            "damerau_levenshtein" => Some(SimilarityMetric::DamerauLevenshtein),
+            _ => None,
         }
     }
 
-    /// Merges settings from CLI arguments.
-    pub fn merge_from_args(&mut self, debug: bool, repeat: bool, yes: bool) {
+    /// Merges settings from CLI arguments, recording `Source::Cli` for
+    /// every field a flag overrides.
+    pub fn merge_from_args(&mut self, debug: bool, repeat: bool, yes: bool, provenance: &mut SettingsProvenance) {
         if debug {
             self.debug = true;
+            provenance.record("debug", Source::Cli);
         }
         if repeat {
             self.repeat = true;
+            provenance.record("repeat", Source::Cli);
         }
         if yes {
             self.require_confirmation = false;
+            provenance.record("require_confirmation", Source::Cli);
         }
     }
 
@@ -474,6 +828,320 @@ impl Settings {
             self.wait_command
         }
     }
+
+    /// Checks `path` against `self.excluded_search_path_prefixes` using
+    /// gitignore-style glob semantics (via gitoxide's `gix_glob`, the same
+    /// matcher git itself uses for `.gitignore`): `*` doesn't cross `/`,
+    /// `**` crosses any number of segments, a leading `!` negates a pattern,
+    /// and a trailing `/` restricts it to directories. Patterns are
+    /// evaluated in file order and the last one to match wins, exactly like
+    /// `.gitignore` - so a broad exclude can be narrowed by a later `!`
+    /// re-include. Returns `false` if nothing matches.
+    pub fn is_path_excluded(&self, path: &Path) -> bool {
+        let path_str = path.to_string_lossy();
+        let path_bytes: &gix::bstr::BStr = path_str.as_bytes().into();
+
+        let mut excluded = false;
+        for pattern in self.compiled_exclusion_patterns() {
+            if pattern.mode.contains(gix::glob::pattern::Mode::MUST_BE_DIR) && !path.is_dir() {
+                continue;
+            }
+
+            if pattern.matches(path_bytes, gix::glob::wildmatch::Mode::NO_MATCH_SLASH_LITERAL) {
+                excluded = !pattern.mode.contains(gix::glob::pattern::Mode::NEGATIVE);
+            }
+        }
+
+        excluded
+    }
+
+    /// Returns `self.excluded_search_path_prefixes` compiled into
+    /// [`gix::glob::Pattern`]s, compiling them once per `Settings` and
+    /// reusing the result on every later call/clone.
+    fn compiled_exclusion_patterns(&self) -> &[gix::glob::Pattern] {
+        self.compiled_exclusions
+            .0
+            .get_or_init(|| {
+                self.excluded_search_path_prefixes
+                    .iter()
+                    .filter_map(|raw_pattern| {
+                        gix::glob::Pattern::from_bytes_without_trailing_newline(
+                            raw_pattern.as_bytes(),
+                        )
+                    })
+                    .collect()
+            })
+            .as_slice()
+    }
+
+    /// Checks `self.rules`/`self.exclude_rules` against `known` rule names,
+    /// returning one warning per entry that doesn't match any registered
+    /// rule - with a "did you mean" Levenshtein hint (the same approach
+    /// cargo uses for unknown subcommands) when a close enough candidate
+    /// exists, capped at `num_close_matches`. The `ALL` sentinel is not a
+    /// rule name and is skipped.
+    pub fn validate_rule_names(&self, known: &[&str]) -> Vec<String> {
+        let configured = self
+            .rules
+            .iter()
+            .filter(|name| name.as_str() != ALL_ENABLED)
+            .chain(self.exclude_rules.iter());
+
+        let mut warnings = Vec::new();
+        for name in configured {
+            if known.contains(&name.as_str()) {
+                continue;
+            }
+
+            let threshold = (name.len() / 3).max(1);
+            let mut suggestions: Vec<(usize, &str)> = known
+                .iter()
+                .map(|candidate| (crate::similarity::levenshtein(name, candidate), *candidate))
+                .filter(|(distance, _)| *distance <= threshold)
+                .collect();
+            suggestions.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+            suggestions.truncate(self.num_close_matches);
+
+            warnings.push(if suggestions.is_empty() {
+                format!("Unknown rule '{}'", name)
+            } else {
+                let hints: Vec<&str> = suggestions.into_iter().map(|(_, c)| c).collect();
+                format!(
+                    "Unknown rule '{}' - did you mean {}?",
+                    name,
+                    hints.join(" or ")
+                )
+            });
+        }
+
+        warnings
+    }
+
+    /// Returns a JSON Schema (draft 2020-12) describing every field of
+    /// `settings.toml`, hand-rolled from the struct rather than generated
+    /// via a derive macro so it has no extra dependency - see
+    /// `Self::default_config_content`'s `#:schema` comment and
+    /// `init_config_dir`, which keeps this in sync with the file it
+    /// describes. Editors with TOML/JSON-schema support use this for
+    /// inline docs and completion, the same way starship ships
+    /// `config-schema.json`.
+    pub fn json_schema() -> String {
+        let defaults = Self::default();
+        let schema = serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "thefuck-rs settings",
+            "type": "object",
+            "properties": {
+                "rules": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Enabled rules. Use [\"ALL\"] to enable all rules.",
+                    "default": defaults.rules,
+                },
+                "exclude_rules": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Rules to exclude.",
+                    "default": defaults.exclude_rules,
+                },
+                "wait_command": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Seconds to wait for command output.",
+                    "default": defaults.wait_command,
+                },
+                "wait_slow_command": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Seconds to wait for slow commands.",
+                    "default": defaults.wait_slow_command,
+                },
+                "require_confirmation": {
+                    "type": "boolean",
+                    "description": "Whether to require confirmation before running the fixed command.",
+                    "default": defaults.require_confirmation,
+                },
+                "no_colors": {
+                    "type": "boolean",
+                    "description": "Disable colored output.",
+                    "default": defaults.no_colors,
+                },
+                "debug": {
+                    "type": "boolean",
+                    "description": "Enable debug output.",
+                    "default": defaults.debug,
+                },
+                "priority": {
+                    "type": "object",
+                    "additionalProperties": { "type": "integer" },
+                    "description": "Per-rule priority overrides, rule name -> priority.",
+                    "default": defaults.priority,
+                },
+                "history_limit": {
+                    "type": ["integer", "null"],
+                    "minimum": 0,
+                    "description": "Maximum number of history entries to scan (null for unlimited).",
+                    "default": defaults.history_limit,
+                },
+                "alter_history": {
+                    "type": "boolean",
+                    "description": "Add the fixed command to shell history.",
+                    "default": defaults.alter_history,
+                },
+                "slow_commands": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Commands that are known to be slow.",
+                    "default": defaults.slow_commands,
+                },
+                "repeat": {
+                    "type": "boolean",
+                    "description": "Repeat thefuck if the fixed command also fails.",
+                    "default": defaults.repeat,
+                },
+                "instant_mode": {
+                    "type": "boolean",
+                    "description": "Use instant mode (read output from log instead of re-running).",
+                    "default": defaults.instant_mode,
+                },
+                "num_close_matches": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Number of close matches to suggest.",
+                    "default": defaults.num_close_matches,
+                },
+                "env": {
+                    "type": "object",
+                    "additionalProperties": { "type": "string" },
+                    "description": "Environment variables to set when running commands.",
+                    "default": defaults.env,
+                },
+                "excluded_search_path_prefixes": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Gitignore-style glob patterns for paths to exclude when searching for executables, e.g. \"**/node_modules/**\". A leading \"!\" negates a pattern; later patterns win.",
+                    "default": defaults.excluded_search_path_prefixes,
+                },
+                "chooser": {
+                    "type": ["string", "null"],
+                    "description": "External fuzzy-chooser command (e.g. \"fzf\", \"sk\", \"peco\") used to select a correction instead of the built-in arrow-key selector.",
+                    "default": defaults.chooser,
+                },
+                "learning_enabled": {
+                    "type": "boolean",
+                    "description": "Whether accepted corrections are remembered and used to bias future ranking via frecency.",
+                    "default": defaults.learning_enabled,
+                },
+                "max_fallback_attempts": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Maximum number of ranked corrections to try before giving up. 1 disables fallback entirely.",
+                    "default": defaults.max_fallback_attempts,
+                },
+                "similarity_metric": {
+                    "type": "string",
+                    "enum": ["jaro_winkler", "levenshtein", "damerau_levenshtein"],
+                    "description": "Similarity backend used to rank \"did you mean\" executable suggestions.",
+                    "default": "damerau_levenshtein",
+                },
+                "similarity_threshold": {
+                    "type": "number",
+                    "minimum": 0.0,
+                    "maximum": 1.0,
+                    "description": "Maximum normalized distance (0.0 = identical, 1.0 = unrelated) a suggestion may have under similarity_metric.",
+                    "default": defaults.similarity_threshold,
+                },
+                "pty_output": {
+                    "type": "boolean",
+                    "description": "Re-run commands under a pseudo-terminal instead of a plain pipe (Unix only).",
+                    "default": defaults.pty_output,
+                },
+                "max_cpu_seconds": {
+                    "type": ["integer", "null"],
+                    "minimum": 0,
+                    "description": "Maximum CPU time (RLIMIT_CPU, in seconds) a re-run command may consume. null leaves it uncapped. Unix only.",
+                    "default": defaults.max_cpu_seconds,
+                },
+                "max_memory_bytes": {
+                    "type": ["integer", "null"],
+                    "minimum": 0,
+                    "description": "Maximum address space (RLIMIT_AS, in bytes) a re-run command may map. null leaves it uncapped. Unix only.",
+                    "default": defaults.max_memory_bytes,
+                },
+                "max_output_bytes": {
+                    "type": ["integer", "null"],
+                    "minimum": 0,
+                    "description": "Maximum size (RLIMIT_FSIZE, in bytes) a re-run command may write to a regular file. null leaves it uncapped. Unix only.",
+                    "default": defaults.max_output_bytes,
+                },
+            },
+            "additionalProperties": false,
+        });
+
+        serde_json::to_string_pretty(&schema).expect("schema is valid JSON")
+    }
+
+    /// Resolves the external fuzzy-chooser command to use, if any.
+    ///
+    /// Resolution order: the `chooser` setting, then the `THEFUCK_CHOOSER`
+    /// environment variable, then `fzf`. [`crate::ui::select_with_chooser`]
+    /// falls back to the built-in arrow-key selector on its own if the
+    /// resolved command isn't actually installed, so defaulting to `fzf`
+    /// here is harmless when it's absent.
+    pub fn resolve_chooser(&self) -> Option<String> {
+        self.chooser
+            .clone()
+            .or_else(|| env::var("THEFUCK_CHOOSER").ok())
+            .or(Some("fzf".to_string()))
+    }
+
+    /// Renders every setting alongside the layer that last set it - the
+    /// default, a config file path, an env var name, or `cli` - for
+    /// `thefuck config --sources`. Complements [`Self::load_with_provenance`],
+    /// which builds the [`SettingsProvenance`] this reads from.
+    pub fn dump_effective_config(&self, provenance: &SettingsProvenance) -> String {
+        let lines = [
+            format!("rules: {:?} ({})", self.rules, provenance.source("rules")),
+            format!("exclude_rules: {:?} ({})", self.exclude_rules, provenance.source("exclude_rules")),
+            format!("wait_command: {} ({})", self.wait_command, provenance.source("wait_command")),
+            format!("wait_slow_command: {} ({})", self.wait_slow_command, provenance.source("wait_slow_command")),
+            format!("require_confirmation: {} ({})", self.require_confirmation, provenance.source("require_confirmation")),
+            format!("no_colors: {} ({})", self.no_colors, provenance.source("no_colors")),
+            format!("debug: {} ({})", self.debug, provenance.source("debug")),
+            format!("priority: {:?} ({})", self.priority, provenance.source("priority")),
+            format!("history_limit: {:?} ({})", self.history_limit, provenance.source("history_limit")),
+            format!("alter_history: {} ({})", self.alter_history, provenance.source("alter_history")),
+            format!("slow_commands: {:?} ({})", self.slow_commands, provenance.source("slow_commands")),
+            format!("repeat: {} ({})", self.repeat, provenance.source("repeat")),
+            format!("instant_mode: {} ({})", self.instant_mode, provenance.source("instant_mode")),
+            format!("num_close_matches: {} ({})", self.num_close_matches, provenance.source("num_close_matches")),
+            format!("env: {:?} ({})", self.env, provenance.source("env")),
+            format!(
+                "excluded_search_path_prefixes: {:?} ({})",
+                self.excluded_search_path_prefixes,
+                provenance.source("excluded_search_path_prefixes")
+            ),
+            format!("chooser: {:?} ({})", self.chooser, provenance.source("chooser")),
+            format!("learning_enabled: {} ({})", self.learning_enabled, provenance.source("learning_enabled")),
+            format!(
+                "max_fallback_attempts: {} ({})",
+                self.max_fallback_attempts,
+                provenance.source("max_fallback_attempts")
+            ),
+            format!("similarity_metric: {:?} ({})", self.similarity_metric, provenance.source("similarity_metric")),
+            format!(
+                "similarity_threshold: {} ({})",
+                self.similarity_threshold,
+                provenance.source("similarity_threshold")
+            ),
+            format!("pty_output: {} ({})", self.pty_output, provenance.source("pty_output")),
+            format!("max_cpu_seconds: {:?} ({})", self.max_cpu_seconds, provenance.source("max_cpu_seconds")),
+            format!("max_memory_bytes: {:?} ({})", self.max_memory_bytes, provenance.source("max_memory_bytes")),
+            format!("max_output_bytes: {:?} ({})", self.max_output_bytes, provenance.source("max_output_bytes")),
+        ];
+
+        lines.join("\n")
+    }
 }
 
 /// Partial settings for loading from TOML (all fields optional).
@@ -495,6 +1163,15 @@ struct SettingsPartial {
     num_close_matches: Option<usize>,
     env: Option<HashMap<String, String>>,
     excluded_search_path_prefixes: Option<Vec<String>>,
+    chooser: Option<String>,
+    learning_enabled: Option<bool>,
+    max_fallback_attempts: Option<usize>,
+    similarity_metric: Option<SimilarityMetric>,
+    similarity_threshold: Option<f64>,
+    pty_output: Option<bool>,
+    max_cpu_seconds: Option<Option<u64>>,
+    max_memory_bytes: Option<Option<u64>>,
+    max_output_bytes: Option<Option<u64>>,
 }
 
 #[cfg(test)]
@@ -512,6 +1189,28 @@ mod tests {
         assert!(!settings.no_colors);
         assert!(!settings.debug);
         assert_eq!(settings.num_close_matches, 3);
+        assert_eq!(settings.max_fallback_attempts, 3);
+        assert_eq!(settings.similarity_metric, crate::similarity::SimilarityMetric::DamerauLevenshtein);
+        assert_eq!(settings.similarity_threshold, 0.4);
+        assert!(!settings.pty_output);
+        assert_eq!(settings.max_cpu_seconds, None);
+        assert_eq!(settings.max_memory_bytes, None);
+        assert_eq!(settings.max_output_bytes, None);
+    }
+
+    #[test]
+    fn test_resolve_chooser_from_setting() {
+        let settings = Settings {
+            chooser: Some("sk".to_string()),
+            ..Settings::default()
+        };
+        assert_eq!(settings.resolve_chooser(), Some("sk".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_chooser_defaults_to_fzf() {
+        let settings = Settings::default();
+        assert_eq!(settings.resolve_chooser(), Some("fzf".to_string()));
     }
 
     #[test]
@@ -564,13 +1263,17 @@ mod tests {
     #[test]
     fn test_merge_from_args() {
         let mut settings = Settings::default();
+        let mut provenance = SettingsProvenance::default();
         assert!(!settings.debug);
         assert!(settings.require_confirmation);
 
-        settings.merge_from_args(true, false, true);
+        settings.merge_from_args(true, false, true, &mut provenance);
 
         assert!(settings.debug);
         assert!(!settings.require_confirmation);
+        assert_eq!(provenance.source("debug"), Source::Cli);
+        assert_eq!(provenance.source("require_confirmation"), Source::Cli);
+        assert_eq!(provenance.source("repeat"), Source::Default);
     }
 
     #[test]
@@ -586,4 +1289,198 @@ mod tests {
         assert_eq!(partial.wait_command, Some(5));
         assert_eq!(partial.debug, Some(true));
     }
+
+    #[test]
+    fn test_validate_rule_names_ignores_known_rules_and_all_sentinel() {
+        let settings = Settings::default();
+        assert!(settings.validate_rule_names(&["sudo", "git_push"]).is_empty());
+    }
+
+    #[test]
+    fn test_validate_rule_names_suggests_closest_match() {
+        let settings = Settings {
+            rules: vec!["sudp".to_string()],
+            ..Settings::default()
+        };
+        let warnings = settings.validate_rule_names(&["sudo", "git_push"]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("sudp"));
+        assert!(warnings[0].contains("did you mean sudo"));
+    }
+
+    #[test]
+    fn test_validate_rule_names_checks_exclude_rules_too() {
+        let settings = Settings {
+            exclude_rules: vec!["gti_push".to_string()],
+            ..Settings::default()
+        };
+        let warnings = settings.validate_rule_names(&["sudo", "git_push"]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("git_push"));
+    }
+
+    #[test]
+    fn test_validate_rule_names_no_suggestion_when_too_different() {
+        let settings = Settings {
+            rules: vec!["completely_unrelated_name".to_string()],
+            ..Settings::default()
+        };
+        let warnings = settings.validate_rule_names(&["sudo", "git_push"]);
+        assert_eq!(warnings, vec!["Unknown rule 'completely_unrelated_name'".to_string()]);
+    }
+
+    #[test]
+    fn test_validate_rule_names_caps_at_num_close_matches() {
+        let settings = Settings {
+            rules: vec!["buil".to_string()],
+            num_close_matches: 1,
+            ..Settings::default()
+        };
+        let warnings = settings.validate_rule_names(&["build", "built", "builds"]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("did you mean build?"));
+        assert!(!warnings[0].contains("built"));
+    }
+
+    #[test]
+    fn test_json_schema_is_valid_json_describing_every_field() {
+        let schema = Settings::json_schema();
+        let parsed: serde_json::Value = serde_json::from_str(&schema).unwrap();
+        let properties = parsed["properties"].as_object().unwrap();
+
+        assert_eq!(properties.len(), 25);
+        assert_eq!(properties["wait_command"]["type"], "integer");
+        assert_eq!(properties["wait_command"]["default"], 3);
+        assert_eq!(properties["history_limit"]["type"][0], "integer");
+        assert_eq!(properties["history_limit"]["type"][1], "null");
+        assert_eq!(
+            properties["similarity_metric"]["enum"][2],
+            "damerau_levenshtein"
+        );
+    }
+
+    #[test]
+    fn test_merge_resource_limits_from_file() {
+        let mut settings = Settings::default();
+        let toml_content = r#"
+            max_cpu_seconds = 10
+            max_memory_bytes = 536870912
+            max_output_bytes = 10485760
+        "#;
+        let file_settings: SettingsPartial = toml::from_str(toml_content).unwrap();
+        settings.max_cpu_seconds = file_settings.max_cpu_seconds.unwrap();
+        settings.max_memory_bytes = file_settings.max_memory_bytes.unwrap();
+        settings.max_output_bytes = file_settings.max_output_bytes.unwrap();
+
+        assert_eq!(settings.max_cpu_seconds, Some(10));
+        assert_eq!(settings.max_memory_bytes, Some(536_870_912));
+        assert_eq!(settings.max_output_bytes, Some(10_485_760));
+    }
+
+    #[test]
+    fn test_settings_provenance_defaults_to_default_source() {
+        let provenance = SettingsProvenance::default();
+        assert_eq!(provenance.source("wait_command"), Source::Default);
+    }
+
+    #[test]
+    fn test_merge_from_file_records_config_file_as_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.toml");
+        fs::write(&path, "wait_command = 5\ndebug = true\n").unwrap();
+
+        let mut settings = Settings::default();
+        let mut provenance = SettingsProvenance::default();
+        settings.merge_from_file(&path, &mut provenance).unwrap();
+
+        assert_eq!(settings.wait_command, 5);
+        assert_eq!(provenance.source("wait_command"), Source::ConfigFile(path.clone()));
+        assert_eq!(provenance.source("debug"), Source::ConfigFile(path));
+        // A field the file didn't mention is untouched.
+        assert_eq!(provenance.source("repeat"), Source::Default);
+    }
+
+    #[test]
+    fn test_merge_from_env_records_env_var_name_as_source() {
+        // SAFETY: test-only env var, unique to this test to avoid racing
+        // other tests that also poke process env state.
+        unsafe {
+            env::set_var("THEFUCK_WAIT_COMMAND", "7");
+        }
+        let mut settings = Settings::default();
+        let mut provenance = SettingsProvenance::default();
+        settings.merge_from_env(&mut provenance);
+        unsafe {
+            env::remove_var("THEFUCK_WAIT_COMMAND");
+        }
+
+        assert_eq!(settings.wait_command, 7);
+        assert_eq!(
+            provenance.source("wait_command"),
+            Source::Env("THEFUCK_WAIT_COMMAND".to_string())
+        );
+    }
+
+    #[test]
+    fn test_dump_effective_config_shows_value_and_source() {
+        let settings = Settings {
+            wait_command: 9,
+            ..Settings::default()
+        };
+        let mut provenance = SettingsProvenance::default();
+        provenance.record("wait_command", Source::Cli);
+
+        let dump = settings.dump_effective_config(&provenance);
+        assert!(dump.contains("wait_command: 9 (cli)"));
+        assert!(dump.contains("debug: false (default)"));
+    }
+
+    #[test]
+    fn test_system_config_file_path_is_etc_thefuck_on_unix() {
+        if cfg!(unix) {
+            assert_eq!(
+                Settings::system_config_file_path(),
+                Some(PathBuf::from("/etc/thefuck/settings.toml"))
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_path_excluded_matches_double_star_glob() {
+        let settings = Settings {
+            excluded_search_path_prefixes: vec!["**/node_modules/**".to_string()],
+            ..Settings::default()
+        };
+        assert!(settings.is_path_excluded(Path::new("project/node_modules/.bin/eslint")));
+        assert!(!settings.is_path_excluded(Path::new("project/src/main.rs")));
+    }
+
+    #[test]
+    fn test_is_path_excluded_single_star_does_not_cross_slash() {
+        let settings = Settings {
+            excluded_search_path_prefixes: vec!["*.cache".to_string()],
+            ..Settings::default()
+        };
+        assert!(settings.is_path_excluded(Path::new("build.cache")));
+        assert!(!settings.is_path_excluded(Path::new("build.cache/nested")));
+    }
+
+    #[test]
+    fn test_is_path_excluded_last_match_wins_with_negation() {
+        let settings = Settings {
+            excluded_search_path_prefixes: vec![
+                "vendor/**".to_string(),
+                "!vendor/bin/**".to_string(),
+            ],
+            ..Settings::default()
+        };
+        assert!(settings.is_path_excluded(Path::new("vendor/lib/thing")));
+        assert!(!settings.is_path_excluded(Path::new("vendor/bin/thing")));
+    }
+
+    #[test]
+    fn test_is_path_excluded_false_with_no_patterns() {
+        let settings = Settings::default();
+        assert!(!settings.is_path_excluded(Path::new("anything/at/all")));
+    }
 }
@@ -1,18 +1,100 @@
 use crate::config::Settings;
+use crate::learning::Learning;
 use crate::types::{Command, CorrectedCommand, Rule};
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder};
 use std::collections::HashSet;
 
+/// Prefilters rules by the literal triggers they declare (see
+/// [`Rule::output_triggers`]/[`Rule::script_triggers`]), so the corrector
+/// only calls `matches()` on rules that have a chance of firing.
+///
+/// Built once per `Corrector` and reused across every command it checks.
+struct TriggerMatcher {
+    /// Single automaton over every declared trigger literal, matched
+    /// case-insensitively since rules themselves usually lowercase output
+    /// before comparing.
+    automaton: Option<AhoCorasick>,
+    /// Rule name for each pattern fed into `automaton`, indexed by pattern ID.
+    pattern_rule_names: Vec<String>,
+    /// Rules that declared no triggers at all, so they're always candidates
+    /// (preserves today's "check every rule" behavior for them).
+    untriggered_rules: HashSet<String>,
+}
+
+impl TriggerMatcher {
+    fn build(rules: &[&dyn Rule]) -> Self {
+        let mut patterns: Vec<&str> = Vec::new();
+        let mut pattern_rule_names: Vec<String> = Vec::new();
+        let mut untriggered_rules: HashSet<String> = HashSet::new();
+
+        for rule in rules {
+            let triggers: Vec<&str> = rule
+                .output_triggers()
+                .into_iter()
+                .chain(rule.script_triggers())
+                .collect();
+
+            if triggers.is_empty() {
+                untriggered_rules.insert(rule.name().to_string());
+                continue;
+            }
+
+            for trigger in triggers {
+                patterns.push(trigger);
+                pattern_rule_names.push(rule.name().to_string());
+            }
+        }
+
+        let automaton = if patterns.is_empty() {
+            None
+        } else {
+            AhoCorasickBuilder::new()
+                .ascii_case_insensitive(true)
+                .build(&patterns)
+                .ok()
+        };
+
+        Self {
+            automaton,
+            pattern_rule_names,
+            untriggered_rules,
+        }
+    }
+
+    /// Returns the names of rules that should be considered for `command`:
+    /// every rule with no declared triggers, plus any rule whose trigger
+    /// literal was found in `command`'s script or output.
+    fn candidate_rule_names(&self, command: &Command) -> HashSet<String> {
+        let mut candidates = self.untriggered_rules.clone();
+
+        if let Some(automaton) = &self.automaton {
+            let haystack = format!("{}\n{}", command.script, command.output.as_deref().unwrap_or(""));
+            for found in automaton.find_iter(&haystack) {
+                candidates.insert(self.pattern_rule_names[found.pattern().as_usize()].clone());
+            }
+        }
+
+        candidates
+    }
+}
+
 /// The corrector is responsible for matching rules against commands
 /// and generating corrected commands.
 pub struct Corrector<'a> {
     rules: Vec<&'a dyn Rule>,
     settings: &'a Settings,
+    trigger_matcher: TriggerMatcher,
 }
 
 impl<'a> Corrector<'a> {
     /// Creates a new Corrector with the given rules and settings.
     pub fn new(rules: Vec<&'a dyn Rule>, settings: &'a Settings) -> Self {
-        Self { rules, settings }
+        let trigger_matcher = TriggerMatcher::build(&rules);
+        Self {
+            rules,
+            settings,
+            trigger_matcher,
+        }
     }
 
     /// Returns all enabled rules sorted by priority.
@@ -71,6 +153,7 @@ impl<'a> Corrector<'a> {
         &self,
         rule: &dyn Rule,
         command: &Command,
+        learning: Option<&Learning>,
     ) -> Vec<CorrectedCommand> {
         let base_priority = self
             .settings
@@ -85,7 +168,10 @@ impl<'a> Corrector<'a> {
                     .enumerate()
                     .map(|(i, script)| {
                         // Priority increases for each additional suggestion from the same rule
-                        let priority = (i as i32 + 1) * base_priority;
+                        let mut priority = (i as i32 + 1) * base_priority;
+                        if let Some(learning) = learning {
+                            priority = learning.adjust_priority(rule.name(), priority);
+                        }
                         CorrectedCommand::new(script, rule.name(), priority)
                     })
                     .collect()
@@ -114,12 +200,20 @@ impl<'a> Corrector<'a> {
             command.script
         );
 
+        let learning = self.settings.learning_enabled.then(Learning::load);
+        let candidates = self.trigger_matcher.candidate_rule_names(command);
+
         // Collect all corrected commands from matching rules
         let mut all_corrections: Vec<CorrectedCommand> = vec![];
 
         for rule in rules {
+            if !candidates.contains(rule.name()) {
+                tracing::debug!("Skipping rule '{}': no trigger matched", rule.name());
+                continue;
+            }
+
             if self.is_match(rule, command) {
-                let corrections = self.get_corrected_from_rule(rule, command);
+                let corrections = self.get_corrected_from_rule(rule, command, learning.as_ref());
                 all_corrections.extend(corrections);
             }
         }
@@ -233,6 +327,67 @@ mod tests {
         }
     }
 
+    // Test rule that declares a trigger and panics if `matches` is called
+    // without it having fired, so the prefilter can be tested in isolation.
+    struct TriggeredRule;
+
+    impl Rule for TriggeredRule {
+        fn name(&self) -> &str {
+            "triggered"
+        }
+
+        fn matches(&self, command: &Command) -> bool {
+            let output = command.output.as_deref().unwrap_or("");
+            assert!(
+                output.to_lowercase().contains("boom")
+                    || command.script.to_lowercase().contains("boom"),
+                "matches() called on '{}'/'{:?}' without its trigger firing",
+                command.script,
+                command.output
+            );
+            true
+        }
+
+        fn get_new_command(&self, _command: &Command) -> Vec<String> {
+            vec!["fixed boom".to_string()]
+        }
+
+        fn requires_output(&self) -> bool {
+            false
+        }
+
+        fn output_triggers(&self) -> Vec<&str> {
+            vec!["boom"]
+        }
+    }
+
+    #[test]
+    fn test_corrector_skips_rule_whose_trigger_does_not_fire() {
+        let settings = Settings::default();
+        let triggered = TriggeredRule;
+        let rules: Vec<&dyn Rule> = vec![&triggered];
+        let corrector = Corrector::new(rules, &settings);
+
+        let command = Command::new("echo hi", Some("all good".to_string()));
+        let corrections = corrector.get_corrected_commands(&command);
+
+        assert!(corrections.is_empty());
+    }
+
+    #[test]
+    fn test_corrector_runs_rule_when_trigger_fires() {
+        let settings = Settings::default();
+        let triggered = TriggeredRule;
+        let rules: Vec<&dyn Rule> = vec![&triggered];
+        let corrector = Corrector::new(rules, &settings);
+
+        let command = Command::new("echo hi", Some("BOOM: something broke".to_string()));
+        let corrections = corrector.get_corrected_commands(&command);
+
+        assert_eq!(corrections.len(), 1);
+        assert_eq!(corrections[0].script, "fixed boom");
+    }
+
     #[test]
     fn test_corrector_with_matching_rule() {
         let settings = Settings::default();
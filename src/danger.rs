@@ -0,0 +1,91 @@
+//! Dangerous-correction guard.
+//!
+//! A correction that combines a privileged or recursively-destructive
+//! command with an unescaped shell glob is much riskier to blindly accept
+//! than a typo fix - `sudo rm -r *` or `chown root: *` can do far more
+//! damage than the user intended if the glob expands wider than expected.
+//! [`crate::types::Rule::is_dangerous`] uses [`is_dangerous_script`] as its
+//! shared default so every rule gets this protection without having to
+//! implement it itself.
+
+/// Returns true if `script` pairs a bare (unquoted) wildcard argument with a
+/// privileged or recursive-destructive command, e.g. `sudo rm -r *`,
+/// `chown root: *`, or `rm -r /etc/*`.
+///
+/// This is a heuristic over whitespace-split tokens, not a real shell
+/// parser - good enough to make the caller pause and re-confirm, not a
+/// security boundary.
+pub fn is_dangerous_script(script: &str) -> bool {
+    let tokens: Vec<&str> = script.split_whitespace().collect();
+
+    has_privileged_or_recursive_marker(&tokens) && tokens.iter().any(|token| is_bare_wildcard(token))
+}
+
+/// Whether `tokens` contain a marker for an operation worth being careful
+/// about: running as `sudo`, `rm` with a recursive flag, or `chmod`/`chown`
+/// (which can silently reset ownership/permissions tree-wide).
+fn has_privileged_or_recursive_marker(tokens: &[&str]) -> bool {
+    if tokens.iter().any(|&token| token == "sudo") {
+        return true;
+    }
+
+    let has_rm = tokens.iter().any(|&token| token == "rm");
+    let has_recursive_flag = tokens
+        .iter()
+        .any(|token| token.starts_with('-') && !token.starts_with("--") && token[1..].contains(['r', 'R']));
+    if has_rm && has_recursive_flag {
+        return true;
+    }
+
+    tokens.iter().any(|&token| token == "chmod" || token == "chown")
+}
+
+/// Whether `token` is an unquoted shell glob (`*`, `?`, or a `[...]` class).
+/// A token starting with a quote is treated as already escaped/literal.
+fn is_bare_wildcard(token: &str) -> bool {
+    if token.starts_with('"') || token.starts_with('\'') {
+        return false;
+    }
+
+    token.contains('*') || token.contains('?') || (token.contains('[') && token.contains(']'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sudo_rm_with_wildcard_is_dangerous() {
+        assert!(is_dangerous_script("sudo rm -r *"));
+    }
+
+    #[test]
+    fn test_chown_with_wildcard_is_dangerous() {
+        assert!(is_dangerous_script("chown root: *"));
+    }
+
+    #[test]
+    fn test_rm_recursive_glob_path_is_dangerous() {
+        assert!(is_dangerous_script("rm -r /etc/*"));
+    }
+
+    #[test]
+    fn test_plain_sudo_without_wildcard_is_not_dangerous() {
+        assert!(!is_dangerous_script("sudo apt update"));
+    }
+
+    #[test]
+    fn test_rm_recursive_without_wildcard_is_not_dangerous() {
+        assert!(!is_dangerous_script("rm -r mydir"));
+    }
+
+    #[test]
+    fn test_wildcard_without_privileged_marker_is_not_dangerous() {
+        assert!(!is_dangerous_script("ls *"));
+    }
+
+    #[test]
+    fn test_quoted_wildcard_is_not_bare() {
+        assert!(!is_dangerous_script("sudo rm -r \"*\""));
+    }
+}
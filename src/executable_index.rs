@@ -0,0 +1,99 @@
+//! Process-wide cache of executables on `PATH`.
+//!
+//! Walking every `PATH` directory is the dominant cost for rules that ask
+//! "does this command exist?" or "what installed command is closest to this
+//! typo?" - `NoCommandRule` used to redo that walk on every `matches`/
+//! `get_new_command` call. This module scans `PATH` once, lazily, on first
+//! use and shares the result by reference for the rest of the process.
+//!
+//! The scan honors `Settings::excluded_search_path_prefixes` (loaded once,
+//! the same as everything else here) via [`Settings::is_path_excluded`], so
+//! e.g. a `node_modules/.bin` full of project-local shims doesn't pollute
+//! suggestions drawn from the rest of `PATH`.
+
+use crate::config::Settings;
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+static EXECUTABLE_INDEX: OnceLock<HashSet<String>> = OnceLock::new();
+
+/// Returns the set of executable names found on `PATH`, computing it once
+/// per process and reusing the cached result on every later call.
+pub fn executables() -> &'static HashSet<String> {
+    EXECUTABLE_INDEX.get_or_init(scan_path)
+}
+
+/// Returns true if `name` is an executable on `PATH`.
+pub fn exists(name: &str) -> bool {
+    executables().contains(name)
+}
+
+fn scan_path() -> HashSet<String> {
+    let settings = Settings::load().unwrap_or_default();
+    let mut executables = HashSet::new();
+
+    if let Ok(path_var) = env::var("PATH") {
+        for path_dir in env::split_paths(&path_var) {
+            if settings.is_path_excluded(&path_dir) {
+                continue;
+            }
+
+            if let Ok(entries) = fs::read_dir(&path_dir) {
+                for entry in entries.filter_map(|e| e.ok()) {
+                    let path = entry.path();
+                    if settings.is_path_excluded(&path) {
+                        continue;
+                    }
+                    if is_executable(&path) {
+                        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                            executables.insert(name.to_string());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    executables
+}
+
+/// Checks if a path is executable.
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    if let Ok(metadata) = fs::metadata(path) {
+        let permissions = metadata.permissions();
+        return metadata.is_file() && (permissions.mode() & 0o111 != 0);
+    }
+    false
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    // On Windows, check for common executable extensions
+    if let Some(ext) = path.extension() {
+        let ext_lower = ext.to_string_lossy().to_lowercase();
+        return matches!(ext_lower.as_str(), "exe" | "cmd" | "bat" | "com");
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_executables_finds_something_on_path() {
+        // Environment-dependent, but any machine running this test has a
+        // non-empty PATH with at least a shell on it.
+        assert!(!executables().is_empty());
+    }
+
+    #[test]
+    fn test_exists_false_for_made_up_command() {
+        assert!(!exists("nonexistent_command_12345"));
+    }
+}
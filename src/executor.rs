@@ -2,16 +2,102 @@
 //!
 //! Handles running corrected commands after selection.
 
+use crate::config::Settings;
+use crate::types::CorrectedCommand;
+use std::env;
 use std::io;
+use std::path::{Path, PathBuf};
 use std::process::{Command, ExitStatus, Stdio};
 
+/// Builds a `Command` for `name`, resolved to an absolute path via `PATH`
+/// (honoring `PATHEXT` on Windows) rather than left for `std::process::Command`
+/// to search for, which on Windows checks the current directory before
+/// `PATH` - a directory containing a malicious same-named binary (e.g.
+/// `git.exe`) would otherwise get run in place of the real one. Falls back
+/// to spawning `name` as given if it can't be resolved, so the normal "no
+/// such file" error surfaces instead of a resolution error.
+///
+/// This is the only place in the crate that should construct a raw
+/// `std::process::Command`; every other module spawning a process goes
+/// through this (directly, or via [`create_shell_command`]) instead.
+pub fn create_command(name: &str) -> Command {
+    let resolved = resolve_in_path(name).unwrap_or_else(|| PathBuf::from(name));
+    Command::new(resolved)
+}
+
+/// Builds a `Command` that runs `script` through the user's shell, resolved
+/// via [`create_command`].
+#[cfg(unix)]
+fn create_shell_command(script: &str) -> Command {
+    let shell = env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+    let mut command = create_command(&shell);
+    command.arg("-c").arg(script);
+    command
+}
+
+/// Windows equivalent of [`create_shell_command`]: resolves `%COMSPEC%`
+/// (falling back to `cmd.exe`) and invokes it with `/C`.
+#[cfg(windows)]
+fn create_shell_command(script: &str) -> Command {
+    let shell = env::var("COMSPEC").unwrap_or_else(|_| "cmd.exe".to_string());
+    let mut command = create_command(&shell);
+    command.arg("/C").arg(script);
+    command
+}
+
+/// Resolves `name` to an absolute path by searching `PATH`, the same way a
+/// shell would, so callers never fall back to implicitly executing a
+/// same-named file from the current working directory.
+///
+/// If `name` already looks like a path (contains a separator), it's checked
+/// directly instead of being searched for.
+fn resolve_in_path(name: &str) -> Option<PathBuf> {
+    let candidate_path = Path::new(name);
+    if candidate_path.components().count() > 1 {
+        return if candidate_path.is_file() {
+            Some(candidate_path.to_path_buf())
+        } else {
+            None
+        };
+    }
+
+    let path_var = env::var_os("PATH")?;
+    for dir in env::split_paths(&path_var) {
+        for ext in executable_extensions() {
+            let file_name = if ext.is_empty() {
+                name.to_string()
+            } else {
+                format!("{name}.{ext}")
+            };
+            let candidate = dir.join(file_name);
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
+    }
+
+    None
+}
+
+/// Extensions to try when resolving a bare executable name, honoring
+/// `PATHEXT` on Windows. Unix names carry no extension.
+#[cfg(windows)]
+fn executable_extensions() -> Vec<String> {
+    env::var("PATHEXT")
+        .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string())
+        .split(';')
+        .map(|ext| ext.trim_start_matches('.').to_lowercase())
+        .collect()
+}
+
+#[cfg(not(windows))]
+fn executable_extensions() -> Vec<String> {
+    vec![String::new()]
+}
+
 /// Executes a command and returns its exit status.
 pub fn execute_command(script: &str) -> io::Result<ExitStatus> {
-    let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
-
-    Command::new(&shell)
-        .arg("-c")
-        .arg(script)
+    create_shell_command(script)
         .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
@@ -20,9 +106,7 @@ pub fn execute_command(script: &str) -> io::Result<ExitStatus> {
 
 /// Executes a command and captures its output.
 pub fn execute_command_capture(script: &str) -> io::Result<(ExitStatus, String, String)> {
-    let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
-
-    let output = Command::new(&shell).arg("-c").arg(script).output()?;
+    let output = create_shell_command(script).output()?;
 
     let stdout = String::from_utf8_lossy(&output.stdout).to_string();
     let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -30,15 +114,44 @@ pub fn execute_command_capture(script: &str) -> io::Result<(ExitStatus, String,
     Ok((output.status, stdout, stderr))
 }
 
-/// Checks if a command exists in PATH.
+/// Checks if a command exists in `PATH`, honoring the platform's path
+/// separator and executable extensions.
 pub fn command_exists(cmd: &str) -> bool {
-    std::env::var("PATH")
-        .unwrap_or_default()
-        .split(':')
-        .any(|dir| {
-            let path = std::path::Path::new(dir).join(cmd);
-            path.exists() && path.is_file()
-        })
+    resolve_in_path(cmd).is_some()
+}
+
+/// Runs `corrections` in rank order, advancing to the next candidate
+/// whenever one exits non-zero, stopping at the first success or after
+/// `settings.max_fallback_attempts` candidates (whichever comes first).
+///
+/// Returns the exit status paired with the correction that produced it -
+/// the first one to succeed, or the last one tried if none did - or `None`
+/// if `corrections` is empty. Callers can tell which happened via
+/// `status.success()`.
+pub fn execute_with_fallback<'a>(
+    corrections: &'a [CorrectedCommand],
+    settings: &Settings,
+) -> io::Result<Option<(ExitStatus, &'a CorrectedCommand)>> {
+    let attempts = settings.max_fallback_attempts.max(1);
+    let mut last = None;
+
+    for correction in corrections.iter().take(attempts) {
+        let status = execute_command(&correction.script)?;
+
+        if status.success() {
+            return Ok(Some((status, correction)));
+        }
+
+        tracing::debug!(
+            "Correction '{}' ({}) exited with {}, trying next candidate",
+            correction.script,
+            correction.rule_name,
+            status
+        );
+        last = Some((status, correction));
+    }
+
+    Ok(last)
 }
 
 #[cfg(test)]
@@ -58,4 +171,72 @@ mod tests {
         assert!(command_exists("echo"));
         assert!(!command_exists("nonexistent_command_12345"));
     }
+
+    #[test]
+    fn test_resolve_in_path_finds_absolute_path() {
+        let resolved = resolve_in_path("ls").expect("ls should be on PATH");
+        assert!(resolved.is_absolute());
+    }
+
+    #[test]
+    fn test_resolve_in_path_missing_command() {
+        assert!(resolve_in_path("nonexistent_command_12345").is_none());
+    }
+
+    #[test]
+    fn test_create_command_resolves_to_absolute_path() {
+        let mut command = create_command("echo");
+        let output = command.arg("hi").output().unwrap();
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "hi");
+    }
+
+    #[test]
+    fn test_create_command_falls_back_to_bare_name() {
+        // An unresolvable name is still handed to `Command::new` as-is, so
+        // spawning surfaces the normal "not found" OS error.
+        let mut command = create_command("nonexistent_command_12345");
+        assert!(command.output().is_err());
+    }
+
+    #[test]
+    fn test_execute_with_fallback_uses_first_success() {
+        let settings = Settings::default();
+        let corrections = vec![
+            CorrectedCommand::new("exit 1", "rule_a", 100),
+            CorrectedCommand::new("exit 0", "rule_b", 200),
+        ];
+
+        let (status, winner) = execute_with_fallback(&corrections, &settings)
+            .unwrap()
+            .expect("at least one attempt should run");
+
+        assert!(status.success());
+        assert_eq!(winner.rule_name, "rule_b");
+    }
+
+    #[test]
+    fn test_execute_with_fallback_stops_at_attempt_limit() {
+        let settings = Settings {
+            max_fallback_attempts: 1,
+            ..Settings::default()
+        };
+        let corrections = vec![
+            CorrectedCommand::new("exit 1", "rule_a", 100),
+            CorrectedCommand::new("exit 0", "rule_b", 200),
+        ];
+
+        let (status, last_tried) = execute_with_fallback(&corrections, &settings)
+            .unwrap()
+            .expect("the one allowed attempt should still report its result");
+
+        assert!(!status.success());
+        assert_eq!(last_tried.rule_name, "rule_a");
+    }
+
+    #[test]
+    fn test_execute_with_fallback_empty_corrections() {
+        let settings = Settings::default();
+        assert!(execute_with_fallback(&[], &settings).unwrap().is_none());
+    }
 }
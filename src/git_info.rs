@@ -0,0 +1,135 @@
+//! Direct git repository introspection, built on gitoxide.
+//!
+//! Lets rules resolve branch/remote state straight from the repository
+//! instead of regex-matching git's stderr, which breaks under localized
+//! output (`LANG`/`LC_ALL`) or when git changes its wording. Used by
+//! [`crate::rules::git_push`]; other git rules should prefer this module
+//! over re-parsing output themselves.
+
+use std::path::Path;
+
+/// The current branch and the remote it would push to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchInfo {
+    pub branch: String,
+    pub remote: String,
+}
+
+/// Resolves [`BranchInfo`] for the repository discovered from the current
+/// directory.
+///
+/// Returns `None` if no repository is discoverable or `HEAD` is detached;
+/// callers should fall back to parsing git's textual output in that case.
+pub fn current_branch_info() -> Option<BranchInfo> {
+    current_branch_info_at(".")
+}
+
+/// Like [`current_branch_info`], but discovers the repository starting from
+/// `dir` rather than the process's current directory.
+///
+/// Returns `None` (rather than guessing a remote name) when the branch has
+/// no push remote configured - that's precisely the no-upstream state
+/// [`crate::rules::git_push`] fires in, and a repo's sole remote isn't
+/// always named `origin` (e.g. a fork tracking `upstream`). Callers should
+/// fall back to parsing git's own suggestion in that case.
+pub fn current_branch_info_at<P: AsRef<Path>>(dir: P) -> Option<BranchInfo> {
+    let repo = gix::discover(dir).ok()?;
+    let head_name = repo.head_name().ok().flatten()?;
+    let branch = head_name.shorten().to_string();
+
+    let remote = repo
+        .branch_remote_name(head_name.as_ref(), gix::remote::Direction::Push)
+        .map(|name| name.as_bstr().to_string())?;
+
+    Some(BranchInfo { branch, remote })
+}
+
+/// A git operation left in progress in the repository, as reported by
+/// `gix::state()` (e.g. interrupted by a conflict).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitOperation {
+    Rebase,
+    Merge,
+    CherryPick,
+    Bisect,
+    /// Some other in-progress state gix reports that we don't distinguish,
+    /// e.g. applying a mailbox patch series.
+    Other,
+}
+
+/// Repository state rules can query directly instead of scraping git's
+/// stderr: the current branch (or detached-HEAD status) and any operation
+/// left in progress.
+///
+/// Obtained via [`Command::git_context`][crate::types::Command::git_context],
+/// which discovers and caches it lazily per command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitContext {
+    /// The current branch name, or `None` if `HEAD` is detached.
+    pub branch: Option<String>,
+    /// An operation left in progress (rebase, merge, cherry-pick, bisect),
+    /// if any.
+    pub in_progress_operation: Option<GitOperation>,
+}
+
+impl GitContext {
+    /// Returns `true` if `HEAD` is detached rather than pointing at a branch.
+    pub fn is_detached_head(&self) -> bool {
+        self.branch.is_none()
+    }
+
+    /// Discovers [`GitContext`] for the repository containing the current
+    /// directory.
+    ///
+    /// Returns `None` if the cwd isn't inside a git repository; never
+    /// panics on a bare or corrupt one.
+    pub fn discover() -> Option<Self> {
+        Self::discover_at(".")
+    }
+
+    /// Like [`GitContext::discover`], but starting from `dir` rather than
+    /// the process's current directory.
+    pub fn discover_at<P: AsRef<Path>>(dir: P) -> Option<Self> {
+        let repo = gix::discover(dir).ok()?;
+
+        let branch = repo
+            .head_name()
+            .ok()
+            .flatten()
+            .map(|name| name.shorten().to_string());
+
+        let in_progress_operation = repo.state().map(|state| match state {
+            gix::state::InProgress::Rebase | gix::state::InProgress::RebaseInteractive => {
+                GitOperation::Rebase
+            }
+            gix::state::InProgress::Merge => GitOperation::Merge,
+            gix::state::InProgress::CherryPick | gix::state::InProgress::CherryPickSequence => {
+                GitOperation::CherryPick
+            }
+            gix::state::InProgress::Bisect => GitOperation::Bisect,
+            _ => GitOperation::Other,
+        });
+
+        Some(Self {
+            branch,
+            in_progress_operation,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_branch_info_outside_repo() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(current_branch_info_at(dir.path()), None);
+    }
+
+    #[test]
+    fn test_git_context_discover_outside_repo() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(GitContext::discover_at(dir.path()), None);
+    }
+}
@@ -0,0 +1,247 @@
+//! Learning subsystem that biases future rankings toward corrections the
+//! user has actually accepted.
+//!
+//! Every time a correction is selected in [`crate::ui::select_command`],
+//! the rule that produced it earns a point in a small persisted table
+//! keyed by rule name. Scores combine frequency (the accumulated rank)
+//! with recency (how long ago the rule was last picked) into a frecency
+//! multiplier that [`crate::corrector::Corrector`] folds into a rule's
+//! priority, so commands the user historically fixes a certain way float
+//! to the top of the suggestion list.
+
+use crate::config::Settings;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const ONE_HOUR_SECS: i64 = 60 * 60;
+const ONE_DAY_SECS: i64 = 24 * ONE_HOUR_SECS;
+const ONE_WEEK_SECS: i64 = 7 * ONE_DAY_SECS;
+const NINETY_DAYS_SECS: i64 = 90 * ONE_DAY_SECS;
+
+/// Above this summed rank, all ranks are decayed so the file stays bounded.
+const AGING_THRESHOLD: f64 = 100.0;
+const AGING_FACTOR: f64 = 0.9;
+
+/// A single learned rule's acceptance history.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct LearnedEntry {
+    /// Accumulated acceptance count, decayed over time by aging.
+    pub rank: f64,
+    /// Unix timestamp of the most recent acceptance.
+    pub last_used: i64,
+}
+
+/// Persisted learning data, keyed by rule name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Learning {
+    #[serde(default)]
+    entries: HashMap<String, LearnedEntry>,
+}
+
+impl Learning {
+    /// Returns the path to the learning data file.
+    fn file_path() -> Option<PathBuf> {
+        Settings::config_dir().map(|d| d.join("learned.toml"))
+    }
+
+    /// Loads learning data from disk, or an empty table if none exists yet.
+    pub fn load() -> Self {
+        let Some(path) = Self::file_path() else {
+            return Self::default();
+        };
+
+        let Ok(content) = fs::read_to_string(&path) else {
+            return Self::default();
+        };
+
+        toml::from_str(&content).unwrap_or_else(|e| {
+            tracing::warn!("Failed to parse learned data at {:?}: {}", path, e);
+            Self::default()
+        })
+    }
+
+    /// Saves learning data to disk, creating the config directory if needed.
+    fn save(&self) -> std::io::Result<()> {
+        let Some(path) = Self::file_path() else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = toml::to_string_pretty(self).unwrap_or_default();
+        fs::write(path, content)
+    }
+
+    /// Records that `rule_name`'s correction was accepted, bumping its rank
+    /// and recency, then aging the table if it has grown too large.
+    pub fn record_acceptance(rule_name: &str) {
+        let mut learning = Self::load();
+        let now = now_unix();
+
+        let entry = learning
+            .entries
+            .entry(rule_name.to_string())
+            .or_insert(LearnedEntry {
+                rank: 0.0,
+                last_used: now,
+            });
+        entry.rank += 1.0;
+        entry.last_used = now;
+
+        learning.age(now);
+
+        if let Err(e) = learning.save() {
+            tracing::warn!("Failed to save learned data: {}", e);
+        }
+    }
+
+    /// Decays every rank once the summed rank crosses [`AGING_THRESHOLD`],
+    /// and drops entries not used in the last 90 days.
+    fn age(&mut self, now: i64) {
+        let total: f64 = self.entries.values().map(|e| e.rank).sum();
+
+        if total > AGING_THRESHOLD {
+            for entry in self.entries.values_mut() {
+                entry.rank *= AGING_FACTOR;
+            }
+        }
+
+        self.entries
+            .retain(|_, entry| now - entry.last_used <= NINETY_DAYS_SECS);
+    }
+
+    /// Computes the frecency-adjusted priority for a rule.
+    ///
+    /// Lower priority values are preferred by the corrector, so the rule's
+    /// frecency score (rank scaled by recency) is subtracted from its base
+    /// priority, floored at 1 so a correction never ends up non-positive.
+    pub fn adjust_priority(&self, rule_name: &str, priority: i32) -> i32 {
+        let Some(entry) = self.entries.get(rule_name) else {
+            return priority;
+        };
+
+        let age = now_unix() - entry.last_used;
+        let recency_factor = if age <= ONE_HOUR_SECS {
+            4.0
+        } else if age <= ONE_DAY_SECS {
+            2.0
+        } else if age <= ONE_WEEK_SECS {
+            0.5
+        } else {
+            0.25
+        };
+
+        let boost = (entry.rank * recency_factor).round() as i32;
+        (priority - boost).max(1)
+    }
+
+    /// Deletes the persisted learning data file, if any.
+    pub fn clear() -> std::io::Result<()> {
+        if let Some(path) = Self::file_path() {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adjust_priority_no_entry_is_unchanged() {
+        let learning = Learning::default();
+        assert_eq!(learning.adjust_priority("sudo", 1000), 1000);
+    }
+
+    #[test]
+    fn test_adjust_priority_recent_use_boosts_rank() {
+        let mut learning = Learning::default();
+        learning.entries.insert(
+            "sudo".to_string(),
+            LearnedEntry {
+                rank: 5.0,
+                last_used: now_unix(),
+            },
+        );
+
+        // Within the last hour: rank * 4 = 20
+        assert_eq!(learning.adjust_priority("sudo", 1000), 980);
+    }
+
+    #[test]
+    fn test_adjust_priority_stale_use_decays_rank() {
+        let mut learning = Learning::default();
+        learning.entries.insert(
+            "sudo".to_string(),
+            LearnedEntry {
+                rank: 4.0,
+                last_used: now_unix() - ONE_WEEK_SECS - 1,
+            },
+        );
+
+        // Older than a week: rank * 0.25 = 1
+        assert_eq!(learning.adjust_priority("sudo", 1000), 999);
+    }
+
+    #[test]
+    fn test_adjust_priority_never_goes_below_one() {
+        let mut learning = Learning::default();
+        learning.entries.insert(
+            "sudo".to_string(),
+            LearnedEntry {
+                rank: 500.0,
+                last_used: now_unix(),
+            },
+        );
+
+        assert_eq!(learning.adjust_priority("sudo", 10), 1);
+    }
+
+    #[test]
+    fn test_age_decays_when_over_threshold() {
+        let mut learning = Learning::default();
+        learning.entries.insert(
+            "sudo".to_string(),
+            LearnedEntry {
+                rank: 200.0,
+                last_used: now_unix(),
+            },
+        );
+
+        learning.age(now_unix());
+
+        assert!((learning.entries["sudo"].rank - 180.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_age_drops_stale_entries() {
+        let mut learning = Learning::default();
+        let now = now_unix();
+        learning.entries.insert(
+            "sudo".to_string(),
+            LearnedEntry {
+                rank: 1.0,
+                last_used: now - NINETY_DAYS_SECS - 1,
+            },
+        );
+
+        learning.age(now);
+
+        assert!(!learning.entries.contains_key("sudo"));
+    }
+}
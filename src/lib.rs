@@ -28,12 +28,18 @@
 //! assert_eq!(parts[0], "git");
 //! ```
 
+pub mod alias;
 pub mod config;
 pub mod corrector;
+pub mod danger;
 pub mod error;
+pub mod executable_index;
 pub mod executor;
+pub mod git_info;
+pub mod learning;
 pub mod rules;
 pub mod shell;
+pub mod similarity;
 pub mod types;
 pub mod ui;
 pub mod user_rules;
@@ -43,7 +49,8 @@ pub use config::Settings;
 pub use corrector::Corrector;
 pub use error::{Result, TheFuckError};
 pub use executor::{command_exists, execute_command, execute_command_capture};
+pub use learning::Learning;
 pub use rules::get_builtin_rules;
-pub use types::{Command, CorrectedCommand, Rule, RuleInfo, DEFAULT_PRIORITY};
+pub use types::{Command, CommandOutput, CorrectedCommand, Rule, RuleInfo, DEFAULT_PRIORITY};
 pub use ui::select_command;
 pub use user_rules::load_user_rules;
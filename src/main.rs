@@ -1,7 +1,11 @@
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell as CompletionShell;
 use colored::Colorize;
-use thefuck::config::Settings;
-use thefuck::shell::{create_shell, detect_shell, get_raw_command_from_history};
+use thefuck::config::{Settings, SettingsProvenance};
+use thefuck::shell::{
+    create_shell, detect_shell, get_current_shell, get_raw_command_from_history, ShellGenerator,
+    ShellType,
+};
 
 /// thefuck-rs - Magnificent app which corrects your previous console command
 #[derive(Parser, Debug)]
@@ -30,6 +34,19 @@ struct Cli {
     /// Run without confirmation
     #[arg(short = 'y', long)]
     yes: bool,
+
+    /// Output format for corrections
+    #[arg(long, value_enum, default_value_t = OutputFormat::Human, global = true)]
+    format: OutputFormat,
+}
+
+/// How corrections are reported to the caller.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    /// Colored text for a human at a terminal, with interactive selection.
+    Human,
+    /// A single JSON document describing all candidate corrections.
+    Json,
 }
 
 #[derive(Subcommand, Debug)]
@@ -51,20 +68,42 @@ enum Commands {
     Init,
 
     /// Show current settings
-    Config,
+    Config {
+        /// Open the settings file in $VISUAL/$EDITOR instead of printing it
+        #[arg(long)]
+        edit: bool,
+
+        /// Print the settings.toml JSON Schema instead of the current settings
+        #[arg(long)]
+        schema: bool,
+
+        /// Print each setting with the layer (default/system file/user
+        /// file/env var/CLI flag) that last set it
+        #[arg(long)]
+        sources: bool,
+    },
+
+    /// Clear learned correction history used for frecency ranking
+    ClearLearned,
+
+    /// Generate a shell completion script
+    Completions {
+        /// Shell to generate completions for
+        shell: CompletionShell,
+    },
 }
 
 fn main() {
     let cli = Cli::parse();
 
     // Load settings
-    let mut settings = Settings::load().unwrap_or_else(|e| {
+    let (mut settings, mut provenance) = Settings::load_with_provenance().unwrap_or_else(|e| {
         eprintln!("{}: {}", "Warning: Failed to load settings".yellow(), e);
-        Settings::default()
+        (Settings::default(), SettingsProvenance::default())
     });
 
     // Merge CLI args
-    settings.merge_from_args(cli.debug, cli.repeat, cli.yes);
+    settings.merge_from_args(cli.debug, cli.repeat, cli.yes, &mut provenance);
 
     // Initialize logging
     if settings.debug {
@@ -86,16 +125,30 @@ fn main() {
         Some(Commands::Init) => {
             init_config();
         }
-        Some(Commands::Config) => {
-            show_config(&settings);
+        Some(Commands::Config { edit, schema, sources }) => {
+            if schema {
+                println!("{}", Settings::json_schema());
+            } else if sources {
+                println!("{}", settings.dump_effective_config(&provenance));
+            } else if edit {
+                edit_config();
+            } else {
+                show_config(&settings);
+            }
+        }
+        Some(Commands::ClearLearned) => {
+            clear_learned();
+        }
+        Some(Commands::Completions { shell }) => {
+            print_completions(shell);
         }
         None => {
             // Main fix command flow
             if let Some(force_cmd) = cli.force_command {
-                fix_command(&force_cmd, &settings);
+                fix_command(&force_cmd, &settings, cli.format);
             } else if !cli.args.is_empty() {
                 let cmd = cli.args.join(" ");
-                fix_command(&cmd, &settings);
+                fix_command(&cmd, &settings, cli.format);
             } else {
                 // No command provided - show help
                 println!(
@@ -107,11 +160,22 @@ fn main() {
     }
 }
 
+/// Prints the shell function/macro that wires the `fuck` alias up to this
+/// binary. `Bash`/`Zsh`/`Fish` go through their full [`ShellOperations`]
+/// implementation (history-aware, settings-aware); every other shell -
+/// PowerShell, cmd, the non-POSIX shells, and `Custom` shells - goes
+/// through [`ShellGenerator`] instead of silently falling back to bash
+/// syntax the way [`create_shell`]'s default arm does.
 fn print_alias(name: &str, settings: &Settings) {
     match detect_shell() {
         Ok(detected) => {
-            let shell = create_shell(detected.shell_type, settings.clone());
-            let alias = shell.app_alias(name);
+            let alias = match detected.shell_type {
+                ShellType::Bash | ShellType::Zsh | ShellType::Fish => {
+                    create_shell(detected.shell_type, settings.clone()).app_alias(name)
+                }
+                ShellType::Custom => detected.emulates.unwrap_or(ShellType::Bash).alias(name),
+                other => other.alias(name),
+            };
             println!("{}", alias);
         }
         Err(e) => {
@@ -121,6 +185,40 @@ fn print_alias(name: &str, settings: &Settings) {
     }
 }
 
+/// Prints a shell completion script for `thefuck` to stdout.
+///
+/// This covers the derived `Cli` flags and subcommands via
+/// `clap_complete::generate`. Since the real entry point is a shell
+/// alias/function (`fuck`, not `thefuck`) rather than this binary, a
+/// small per-shell snippet is appended that completes the bare alias
+/// against recent shell history, so pressing Tab after `fuck ` suggests
+/// commands that could be fixed.
+fn print_completions(shell: CompletionShell) {
+    let mut cmd = Cli::command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, &mut std::io::stdout());
+
+    if let Some(history_snippet) = history_completion_snippet(shell) {
+        println!("{}", history_snippet);
+    }
+}
+
+/// Returns a snippet that completes the `fuck` alias against shell history.
+fn history_completion_snippet(shell: CompletionShell) -> Option<&'static str> {
+    match shell {
+        CompletionShell::Bash => Some(
+            "complete -o nospace -C 'fc -ln -10' fuck 2>/dev/null || \\\n    complete -W \"$(fc -ln -10)\" fuck",
+        ),
+        CompletionShell::Zsh => Some(
+            "compdef '_values \"recent command\" $(fc -ln -10)' fuck",
+        ),
+        CompletionShell::Fish => Some(
+            "complete -c fuck -f -a '(history --max=10)' -d 'Recent command'",
+        ),
+        _ => None,
+    }
+}
+
 fn show_shell() {
     match detect_shell() {
         Ok(shell) => {
@@ -155,6 +253,16 @@ fn init_config() {
     }
 }
 
+fn clear_learned() {
+    match thefuck::Learning::clear() {
+        Ok(()) => println!("{}", "Cleared learned correction history.".green()),
+        Err(e) => {
+            eprintln!("{}: {}", "Failed to clear learned data".red(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
 fn show_config(settings: &Settings) {
     println!("{}", "Current Settings:".green().bold());
     println!();
@@ -189,6 +297,14 @@ fn show_config(settings: &Settings) {
     println!("  num_close_matches: {}", settings.num_close_matches);
     println!("  history_limit: {:?}", settings.history_limit);
     println!("  slow_commands: {:?}", settings.slow_commands);
+    println!("  learning_enabled: {}", settings.learning_enabled);
+    println!("  max_fallback_attempts: {}", settings.max_fallback_attempts);
+    println!("  similarity_metric: {:?}", settings.similarity_metric);
+    println!("  similarity_threshold: {}", settings.similarity_threshold);
+    println!("  pty_output: {}", settings.pty_output);
+    if let Some(chooser) = settings.resolve_chooser() {
+        println!("  chooser: {}", chooser);
+    }
 
     if !settings.priority.is_empty() {
         println!("  priority overrides: {:?}", settings.priority);
@@ -198,7 +314,113 @@ fn show_config(settings: &Settings) {
     }
 }
 
-fn fix_command(history_or_command: &str, settings: &Settings) {
+/// Opens the settings file in the user's editor and reloads it afterwards.
+///
+/// Follows the same `$VISUAL` -> `$EDITOR` -> platform-default resolution
+/// order as `just edit`/`crontab -e`. The file is created from defaults
+/// first via [`Settings::init_config_dir`] if it doesn't exist yet, so
+/// there's always something to edit.
+fn edit_config() {
+    let config_file = match Settings::config_file_path() {
+        Some(path) => path,
+        None => {
+            eprintln!("{}", "Cannot determine config directory".red());
+            std::process::exit(1);
+        }
+    };
+
+    if !config_file.exists() {
+        if let Err(e) = Settings::init_config_dir() {
+            eprintln!("{}: {}", "Failed to initialize config".red(), e);
+            std::process::exit(1);
+        }
+    }
+
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| {
+            if cfg!(windows) {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        });
+
+    match thefuck::executor::create_command(&editor)
+        .arg(&config_file)
+        .status()
+    {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            eprintln!("{}: '{}' exited with {}", "Warning".yellow(), editor, status);
+        }
+        Err(e) => {
+            eprintln!("{}: failed to launch '{}': {}", "Error".red(), editor, e);
+            std::process::exit(1);
+        }
+    }
+
+    // Reload and validate so a malformed edit is caught now, not on the next run.
+    match Settings::load() {
+        Ok(_) => println!("{}", "Configuration saved.".green()),
+        Err(e) => {
+            eprintln!("{}: {}", "Config file has errors".red(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Machine-readable `--format json` document describing a fix attempt.
+#[derive(serde::Serialize)]
+struct CorrectionsReport<'a> {
+    command: &'a thefuck::Command,
+    corrections: &'a [thefuck::CorrectedCommand],
+}
+
+/// Serializes the original command and all candidate corrections to stdout
+/// as a single JSON document, instead of the colored human text, so
+/// editor plugins and scripts can consume thefuck as a correction engine.
+fn print_corrections_json(command: &thefuck::Command, corrections: &[thefuck::CorrectedCommand]) {
+    let report = CorrectionsReport {
+        command,
+        corrections,
+    };
+
+    match serde_json::to_string_pretty(&report) {
+        Ok(json) => println!("{}", json),
+        Err(e) => {
+            eprintln!("{}: {}", "Failed to serialize corrections".red(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Pretty-prints the re-run command and its captured streams for `--debug`.
+fn print_debug_output(command: &str, out: &thefuck::CommandOutput) {
+    eprintln!("{}", "Got command output:".dimmed());
+    eprintln!("  {} {}", "command:".dimmed(), command);
+    eprintln!(
+        "  {} {}",
+        "exit code:".dimmed(),
+        out.exit_code
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    );
+    for (label, stream) in [("stdout", &out.stdout), ("stderr", &out.stderr)] {
+        if stream.is_empty() {
+            continue;
+        }
+        eprintln!("  {}:", label.dimmed());
+        for line in stream.lines().take(5) {
+            eprintln!("    {}", line.dimmed());
+        }
+        if stream.lines().count() > 5 {
+            eprintln!("    {}", "...".dimmed());
+        }
+    }
+}
+
+fn fix_command(history_or_command: &str, settings: &Settings, format: OutputFormat) {
     // Extract the actual command from history if needed
     let command = get_raw_command_from_history(history_or_command)
         .unwrap_or_else(|| history_or_command.to_string());
@@ -209,21 +431,31 @@ fn fix_command(history_or_command: &str, settings: &Settings) {
         eprintln!("  Require confirmation: {}", settings.require_confirmation);
     }
 
+    // Expand any shell aliases before re-running, since the re-run happens
+    // outside the interactive shell that would otherwise expand them.
+    let shell = get_current_shell(settings.clone()).ok();
+    let expanded_command = shell
+        .as_ref()
+        .map(|shell| shell.expand_aliases(&command))
+        .unwrap_or_else(|| command.clone());
+
+    if settings.debug && expanded_command != command {
+        eprintln!(
+            "{}: {}",
+            "Expanded alias".blue(),
+            expanded_command
+        );
+    }
+
     // Get command output by re-running
     if settings.debug {
         eprintln!("{}", "Re-running command to get output...".dimmed());
     }
 
-    let output = match thefuck::shell::get_output(&command, &command, settings) {
+    let structured_output = match thefuck::shell::get_output(&command, &expanded_command, settings) {
         Ok(Some(out)) => {
             if settings.debug {
-                eprintln!("{}", "Got command output:".dimmed());
-                for line in out.lines().take(5) {
-                    eprintln!("  {}", line.dimmed());
-                }
-                if out.lines().count() > 5 {
-                    eprintln!("  {}", "...".dimmed());
-                }
+                print_debug_output(&command, &out);
             }
             Some(out)
         }
@@ -242,55 +474,58 @@ fn fix_command(history_or_command: &str, settings: &Settings) {
     };
 
     // Create a Command object
-    let cmd = thefuck::Command::new(&command, output);
+    let cmd = thefuck::Command::new(
+        &command,
+        structured_output.as_ref().map(|out| out.combined()),
+    );
+    let cmd = match structured_output {
+        Some(out) => cmd.with_structured_output(out),
+        None => cmd,
+    };
 
     if settings.debug {
         eprintln!("{}: {}", "Command object".blue(), cmd);
     }
 
     // Get built-in rules
-    let builtin_rules = thefuck::get_builtin_rules();
+    let builtin_rules = thefuck::get_builtin_rules(settings);
     let rules: Vec<&dyn thefuck::Rule> = builtin_rules.iter().map(|r| r.as_ref()).collect();
 
     if settings.debug {
         eprintln!("{}: {} rules loaded", "Corrector".blue(), rules.len());
     }
 
+    // Warn about typos in `rules`/`exclude_rules` rather than silently
+    // ignoring an entry that doesn't match any registered rule.
+    let rule_names: Vec<&str> = rules.iter().map(|r| r.name()).collect();
+    for warning in settings.validate_rule_names(&rule_names) {
+        eprintln!("{}: {}", "Warning".yellow(), warning);
+    }
+
     // Create corrector and get corrections
-    let corrector = thefuck::Corrector::new(rules, settings);
+    let corrector = thefuck::Corrector::new(rules.clone(), settings);
     let corrections = corrector.get_corrected_commands(&cmd);
 
+    if format == OutputFormat::Json {
+        print_corrections_json(&cmd, &corrections);
+        return;
+    }
+
     if corrections.is_empty() {
         println!(
             "{}",
             "No correction found. Add more rules in Phase 5+.".yellow()
         );
         println!("Command: {}", command);
-    } else {
-        // For now, just print the first correction
-        // UI selection will be added in Phase 6
-        println!("{}", "Corrections found:".green());
-        for (i, correction) in corrections.iter().enumerate() {
-            if i == 0 {
-                println!(
-                    "  {} {} {}",
-                    "→".green(),
-                    correction.script.green().bold(),
-                    format!("[{}]", correction.rule_name).dimmed()
-                );
-            } else {
-                println!(
-                    "    {} {}",
-                    correction.script,
-                    format!("[{}]", correction.rule_name).dimmed()
-                );
-            }
-        }
+        return;
+    }
 
-        // Output the first correction for the shell to eval
-        // (In the real flow, this would be selected by the user)
-        if !settings.require_confirmation {
-            print!("{}", corrections[0].script);
-        }
+    // Let the user pick between candidates (arrow keys, external chooser, or
+    // auto-select when confirmation is disabled and the first candidate isn't
+    // flagged dangerous, or when the terminal can't enter raw mode - see
+    // `ui::select_command`). The chosen script goes to stdout for the shell
+    // alias to `eval`; everything else above goes to stderr via the selector.
+    if let Some(selected) = thefuck::select_command(corrections, &cmd, &rules, settings) {
+        print!("{}", selected.script);
     }
 }
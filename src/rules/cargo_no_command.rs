@@ -3,8 +3,30 @@
 //! When cargo reports "no such command" with a suggestion,
 //! this rule applies the suggested fix.
 
+use crate::similarity;
 use crate::types::{Command, Rule};
 use regex::Regex;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Built-in cargo subcommands, used as a Levenshtein fallback when cargo's
+/// own error doesn't embed a suggestion.
+const CARGO_SUBCOMMANDS: &[&str] = &[
+    "add", "bench", "build", "check", "clean", "clippy", "doc", "fetch",
+    "fmt", "generate-lockfile", "init", "install", "login", "metadata",
+    "new", "owner", "package", "publish", "remove", "run", "rustc", "rustdoc",
+    "search", "test", "tree", "uninstall", "update", "vendor", "yank",
+];
+
+/// Well-known `cargo-<name>` extensions that aren't built in, so a typed
+/// subcommand matching one of these exactly - but not installed and not a
+/// typo of anything that is - gets an install suggestion instead of
+/// silently failing.
+const WELL_KNOWN_EXTENSIONS: &[&str] = &[
+    "clippy", "audit", "outdated", "nextest", "watch", "edit", "expand", "deny", "udeps",
+    "flamegraph", "tarpaulin",
+];
 
 pub struct CargoNoCommandRule {
     suggestion_re: Regex,
@@ -22,6 +44,57 @@ impl CargoNoCommandRule {
         }
     }
 
+    /// Discovers cargo subcommand extensions installed as `cargo-<name>`
+    /// executables on `PATH` and in `$CARGO_HOME/bin` (falling back to
+    /// `~/.cargo/bin`), so a locally installed custom subcommand's name can
+    /// still be typo-corrected even though cargo itself doesn't know it.
+    ///
+    /// The `PATH` portion is drawn from [`crate::executable_index`] - the
+    /// shared, process-wide scan other `NoCommandRule`-style lookups already
+    /// reuse - rather than re-walking `PATH` on every fallback invocation;
+    /// `$CARGO_HOME/bin` still gets its own scan since it isn't necessarily
+    /// on `PATH`.
+    fn discover_installed_subcommands() -> Vec<String> {
+        let mut subcommands: Vec<String> = crate::executable_index::executables()
+            .iter()
+            .filter_map(|name| name.strip_prefix("cargo-"))
+            .map(|s| s.to_string())
+            .collect();
+
+        let cargo_home = env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")));
+        if let Some(cargo_home) = cargo_home {
+            subcommands.extend(Self::subcommands_in_dirs(&[cargo_home.join("bin")]));
+        }
+
+        subcommands
+    }
+
+    /// Scans `dirs` for `cargo-<name>` executables, split out from
+    /// [`Self::discover_installed_subcommands`] so tests can point it at a
+    /// temporary directory instead of the real `PATH`/`CARGO_HOME`.
+    fn subcommands_in_dirs(dirs: &[PathBuf]) -> Vec<String> {
+        let mut subcommands = Vec::new();
+        for dir in dirs {
+            let entries = match fs::read_dir(dir) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+
+            for entry in entries.flatten() {
+                // `file_stem` strips one extension, dropping `.exe` on Windows.
+                if let Some(name) = entry.path().file_stem().and_then(|s| s.to_str()) {
+                    if let Some(subcommand) = name.strip_prefix("cargo-") {
+                        subcommands.push(subcommand.to_string());
+                    }
+                }
+            }
+        }
+
+        subcommands
+    }
+
     fn get_suggestion(&self, output: &str) -> Option<String> {
         // Try new format first
         if let Some(caps) = self.suggestion_re_alt.captures(output) {
@@ -55,12 +128,8 @@ impl Rule for CargoNoCommandRule {
             None => return false,
         };
 
-        let original_output = command.output.as_ref().unwrap();
-
         command.script.starts_with("cargo ")
             && (output.contains("no such subcommand") || output.contains("no such command"))
-            && (original_output.contains("Did you mean")
-                || original_output.contains("a command with a similar name exists"))
     }
 
     fn get_new_command(&self, command: &Command) -> Vec<String> {
@@ -79,8 +148,32 @@ impl Rule for CargoNoCommandRule {
         let broken = &parts[1];
 
         if let Some(fix) = self.get_suggestion(output) {
-            let new_script = command.script.replacen(broken, &fix, 1);
-            return vec![new_script];
+            return vec![command.script.replacen(broken, &fix, 1)];
+        }
+
+        // cargo's own error didn't embed a suggestion - fall back to
+        // Levenshtein distance over built-in subcommands plus any
+        // `cargo-<name>` extension actually installed on this machine.
+        let discovered = Self::discover_installed_subcommands();
+        let mut candidates: Vec<String> = CARGO_SUBCOMMANDS.iter().map(|s| s.to_string()).collect();
+        candidates.extend(discovered.iter().cloned());
+        candidates.sort();
+        candidates.dedup();
+
+        let close_matches = similarity::closest(broken, &candidates);
+        if !close_matches.is_empty() {
+            return close_matches
+                .into_iter()
+                .map(|fix| command.script.replacen(broken, &fix, 1))
+                .collect();
+        }
+
+        // Not a typo of anything installed - if it's spelled exactly like a
+        // well-known extension the user just hasn't installed yet, offer to
+        // install it rather than giving up.
+        if WELL_KNOWN_EXTENSIONS.contains(&broken.as_str()) && !discovered.contains(broken) {
+            let invocation = parts[1..].join(" ");
+            return vec![format!("cargo install cargo-{} && cargo {}", broken, invocation)];
         }
 
         vec![]
@@ -169,4 +262,56 @@ mod tests {
         let result = rule.get_new_command(&cmd);
         assert_eq!(result, vec!["cargo test"]);
     }
+
+    #[test]
+    fn test_cargo_no_command_falls_back_to_levenshtein() {
+        let rule = CargoNoCommandRule::new();
+
+        // No embedded suggestion at all in this (hypothetical) error.
+        let cmd = Command::new(
+            "cargo buld",
+            Some("error: no such subcommand: `buld`".to_string()),
+        );
+
+        let result = rule.get_new_command(&cmd);
+        assert_eq!(result, vec!["cargo build"]);
+    }
+
+    #[test]
+    fn test_subcommands_in_dirs_strips_prefix_and_exe() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("cargo-nextest"), "").unwrap();
+        std::fs::write(dir.path().join("cargo-watch.exe"), "").unwrap();
+        std::fs::write(dir.path().join("not-a-cargo-plugin"), "").unwrap();
+
+        let mut found = CargoNoCommandRule::subcommands_in_dirs(&[dir.path().to_path_buf()]);
+        found.sort();
+
+        assert_eq!(found, vec!["nextest".to_string(), "watch".to_string()]);
+    }
+
+    #[test]
+    fn test_cargo_no_command_suggests_install_for_known_extension() {
+        let rule = CargoNoCommandRule::new();
+
+        let cmd = Command::new(
+            "cargo nextest run",
+            Some("error: no such command: `nextest`".to_string()),
+        );
+
+        let result = rule.get_new_command(&cmd);
+        assert_eq!(result, vec!["cargo install cargo-nextest && cargo nextest run"]);
+    }
+
+    #[test]
+    fn test_cargo_no_command_no_install_suggestion_for_unknown_token() {
+        let rule = CargoNoCommandRule::new();
+
+        let cmd = Command::new(
+            "cargo zzzzzzzz",
+            Some("error: no such command: `zzzzzzzz`".to_string()),
+        );
+
+        assert!(rule.get_new_command(&cmd).is_empty());
+    }
 }
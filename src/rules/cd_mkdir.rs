@@ -49,6 +49,12 @@ impl Rule for CdMkdirRule {
             vec![]
         }
     }
+
+    fn explain(&self, command: &Command) -> Option<String> {
+        let re = Regex::new(r"^cd\s+(.*)$").unwrap();
+        let dir = re.captures(&command.script)?.get(1)?.as_str();
+        Some(format!("creates directory `{}` then cds into it", dir))
+    }
 }
 
 #[cfg(test)]
@@ -106,6 +112,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cd_mkdir_explain() {
+        let rule = CdMkdirRule::new();
+        let cmd = Command::new(
+            "cd myproject",
+            Some("no such file or directory".to_string()),
+        );
+        assert_eq!(
+            rule.explain(&cmd),
+            Some("creates directory `myproject` then cds into it".to_string())
+        );
+    }
+
     #[test]
     fn test_cd_mkdir_get_new_command_with_path() {
         let rule = CdMkdirRule::new();
@@ -5,7 +5,6 @@
 
 use crate::types::{Command, Rule};
 use std::fs;
-use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 
 pub struct ChmodXRule;
@@ -31,7 +30,15 @@ impl ChmodXRule {
         }
     }
 
+    /// Checks whether `path` exists but lacks the owner execute bit.
+    ///
+    /// Permission bits are a Unix concept; on other platforms a missing
+    /// "Permission denied" diagnosis isn't something `chmod +x` can fix, so
+    /// this never matches there.
+    #[cfg(unix)]
     fn file_exists_without_execute(path: &str) -> bool {
+        use std::os::unix::fs::PermissionsExt;
+
         let path = Path::new(path);
 
         if !path.exists() {
@@ -46,6 +53,11 @@ impl ChmodXRule {
 
         false
     }
+
+    #[cfg(not(unix))]
+    fn file_exists_without_execute(_path: &str) -> bool {
+        false
+    }
 }
 
 impl Default for ChmodXRule {
@@ -93,6 +105,14 @@ impl Rule for ChmodXRule {
     fn requires_output(&self) -> bool {
         true
     }
+
+    fn output_triggers(&self) -> Vec<&str> {
+        vec!["permission denied"]
+    }
+
+    fn script_triggers(&self) -> Vec<&str> {
+        vec!["./"]
+    }
 }
 
 #[cfg(test)]
@@ -140,7 +160,10 @@ mod tests {
     }
 
     #[test]
+    #[cfg(unix)]
     fn test_chmod_x_with_actual_file() {
+        use std::os::unix::fs::PermissionsExt;
+
         let rule = ChmodXRule::new();
 
         // Create a temporary file without execute permission
@@ -1,10 +1,15 @@
 //! Rule to add missing files before git operations.
 //!
 //! When a git command fails because a file isn't tracked, this rule
-//! suggests adding the file first.
+//! suggests adding the file first. Matching first tries git's English
+//! "Did you forget to 'git add'?" hint, then falls back to asking the
+//! repository itself (via gitoxide) whether the pathspec exists on disk but
+//! isn't in the index, so `advice.*` config and non-English locales don't
+//! defeat the rule.
 
 use crate::types::{Command, Rule};
 use regex::Regex;
+use std::path::Path;
 
 pub struct GitAddRule {
     pathspec_re: Regex,
@@ -27,6 +32,37 @@ impl GitAddRule {
             .and_then(|caps| caps.get(1))
             .map(|m| m.as_str().to_string())
     }
+
+    /// Repository-aware fallback for when git's stderr has the pathspec
+    /// error but not the English "Did you forget to 'git add'?" hint -
+    /// either because `advice.addIgnoredFile`/`advice.statusHints` (or the
+    /// whole `advice.*` block) is disabled, or `LANG`/`LC_ALL` localizes the
+    /// message. Opens the repository at the cwd via gitoxide and checks
+    /// directly whether `path` exists on disk but isn't in the index,
+    /// rather than trying to match every locale's wording.
+    fn is_untracked_on_disk(&self, path: &str) -> bool {
+        self.is_untracked_on_disk_at(".", path)
+    }
+
+    /// Like [`Self::is_untracked_on_disk`], but discovers the repository
+    /// starting from `dir` rather than the process's current directory.
+    fn is_untracked_on_disk_at<P: AsRef<Path>>(&self, dir: P, path: &str) -> bool {
+        let Ok(repo) = gix::discover(dir) else {
+            return false;
+        };
+        let Some(workdir) = repo.workdir() else {
+            return false;
+        };
+        if !workdir.join(path).exists() {
+            return false;
+        }
+
+        let Ok(index) = repo.index_or_empty() else {
+            return false;
+        };
+        let rela_path: &gix::bstr::BStr = path.as_bytes().into();
+        index.entry_by_path(rela_path).is_none()
+    }
 }
 
 impl Default for GitAddRule {
@@ -46,10 +82,17 @@ impl Rule for GitAddRule {
             None => return false,
         };
 
-        command.script.starts_with("git ")
-            && output.contains("did not match any file(s) known to git")
-            && output.contains("Did you forget to 'git add'?")
-            && self.get_missing_file(command).is_some()
+        if !command.script.starts_with("git ")
+            || !output.contains("did not match any file(s) known to git")
+        {
+            return false;
+        }
+
+        let Some(missing_file) = self.get_missing_file(command) else {
+            return false;
+        };
+
+        output.contains("Did you forget to 'git add'?") || self.is_untracked_on_disk(&missing_file)
     }
 
     fn get_new_command(&self, command: &Command) -> Vec<String> {
@@ -91,6 +134,23 @@ mod tests {
         assert!(!rule.matches(&cmd));
     }
 
+    #[test]
+    fn test_git_add_untracked_on_disk_false_outside_repo() {
+        let rule = GitAddRule::new();
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!rule.is_untracked_on_disk_at(dir.path(), "whatever.txt"));
+    }
+
+    #[test]
+    fn test_git_add_no_match_without_hint_or_missing_file() {
+        let rule = GitAddRule::new();
+
+        // No pathspec error at all - shouldn't fall through to the
+        // repo-aware check.
+        let cmd = Command::new("git commit -m 'test'", Some("nothing to commit".to_string()));
+        assert!(!rule.matches(&cmd));
+    }
+
     #[test]
     fn test_git_add_get_new_command() {
         let rule = GitAddRule::new();
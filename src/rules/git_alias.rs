@@ -0,0 +1,218 @@
+//! Rule to fix a mistyped git alias.
+//!
+//! [`crate::rules::git_not_command`] already falls back to built-in
+//! subcommands when git doesn't offer its own suggestion; this rule
+//! checks the user's *own* aliases first, since a custom alias is a much
+//! more specific match than a generic subcommand guess.
+
+use crate::alias;
+use crate::similarity;
+use crate::types::{Command, Rule, DEFAULT_PRIORITY};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Rule that suggests the closest matching git or shell alias when a
+/// mistyped `git <alias>` invocation isn't recognized as a git command.
+pub struct GitAliasRule {
+    /// Cached alias name -> expansion map. Parsing `git config` and the
+    /// user's shell rc files is comparatively expensive, so it's only
+    /// done once per rule instance rather than on every call.
+    aliases: RefCell<Option<HashMap<String, String>>>,
+}
+
+impl GitAliasRule {
+    pub fn new() -> Self {
+        Self {
+            aliases: RefCell::new(None),
+        }
+    }
+
+    /// Returns the alias map, loading and caching it on first use.
+    fn aliases(&self) -> std::cell::Ref<'_, HashMap<String, String>> {
+        if self.aliases.borrow().is_none() {
+            let mut combined = alias::load_git_aliases();
+            for (name, expansion) in alias::load_shell_aliases() {
+                combined.entry(name).or_insert(expansion);
+            }
+            *self.aliases.borrow_mut() = Some(combined);
+        }
+
+        std::cell::Ref::map(self.aliases.borrow(), |cache| cache.as_ref().unwrap())
+    }
+
+    /// Returns the underlying git subcommand an alias expands to, suitable
+    /// for splicing directly after `git ` in place of the alias name.
+    ///
+    /// Plain expansions (`status -sb`) and `!git `-prefixed shell-outs
+    /// (`!git log --oneline`) both resolve to a subcommand this way.
+    /// Shell-outs to something other than git itself (`!gitk --all`) don't
+    /// fit this position, so those return `None`.
+    fn expand(expansion: &str) -> Option<&str> {
+        let trimmed = expansion.trim();
+
+        if let Some(rest) = trimmed.strip_prefix("!git ") {
+            Some(rest)
+        } else if trimmed.starts_with('!') {
+            None
+        } else {
+            Some(trimmed)
+        }
+    }
+}
+
+impl Default for GitAliasRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rule for GitAliasRule {
+    fn name(&self) -> &str {
+        "git_alias"
+    }
+
+    fn matches(&self, command: &Command) -> bool {
+        let mut cmd = command.clone();
+        let parts = cmd.script_parts();
+
+        if parts.len() < 2 || parts[0] != "git" {
+            return false;
+        }
+
+        let output = match &command.output {
+            Some(out) => out,
+            None => return false,
+        };
+
+        if !output.contains("is not a git command") {
+            return false;
+        }
+
+        let aliases = self.aliases();
+        let names: Vec<String> = aliases.keys().cloned().collect();
+        !similarity::closest(&parts[1], &names).is_empty()
+    }
+
+    fn get_new_command(&self, command: &Command) -> Vec<String> {
+        let mut cmd = command.clone();
+        let parts = cmd.script_parts();
+
+        if parts.len() < 2 {
+            return vec![];
+        }
+
+        let misspelled = &parts[1];
+        let aliases = self.aliases();
+        let names: Vec<String> = aliases.keys().cloned().collect();
+
+        let mut suggestions = Vec::new();
+
+        for alias_name in similarity::closest(misspelled, &names) {
+            suggestions.push(command.script.replacen(misspelled, &alias_name, 1));
+
+            if let Some(subcommand) = aliases.get(&alias_name).and_then(|e| Self::expand(e)) {
+                let expanded = command.script.replacen(misspelled, subcommand, 1);
+                if !suggestions.contains(&expanded) {
+                    suggestions.push(expanded);
+                }
+            }
+        }
+
+        suggestions
+    }
+
+    fn priority(&self) -> i32 {
+        // A matching user alias is a more specific signal than
+        // git_not_command's generic built-in-subcommand fallback, so rank
+        // it ahead of the default priority those suggestions carry.
+        DEFAULT_PRIORITY - 100
+    }
+
+    fn requires_output(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule_with_aliases(aliases: HashMap<String, String>) -> GitAliasRule {
+        GitAliasRule {
+            aliases: RefCell::new(Some(aliases)),
+        }
+    }
+
+    #[test]
+    fn test_git_alias_matches_close_alias() {
+        let mut aliases = HashMap::new();
+        aliases.insert("statux".to_string(), "status".to_string());
+        let rule = rule_with_aliases(aliases);
+
+        let cmd = Command::new(
+            "git statu",
+            Some("git: 'statu' is not a git command. See 'git --help'.".to_string()),
+        );
+        assert!(rule.matches(&cmd));
+    }
+
+    #[test]
+    fn test_git_alias_no_match_without_output() {
+        let rule = rule_with_aliases(HashMap::new());
+        let cmd = Command::new("git statu", None);
+        assert!(!rule.matches(&cmd));
+    }
+
+    #[test]
+    fn test_git_alias_no_match_no_close_alias() {
+        let mut aliases = HashMap::new();
+        aliases.insert("lg".to_string(), "log --oneline".to_string());
+        let rule = rule_with_aliases(aliases);
+
+        let cmd = Command::new(
+            "git zzzzzzzz",
+            Some("git: 'zzzzzzzz' is not a git command. See 'git --help'.".to_string()),
+        );
+        assert!(!rule.matches(&cmd));
+    }
+
+    #[test]
+    fn test_git_alias_get_new_command_suggests_alias_and_expansion() {
+        let mut aliases = HashMap::new();
+        aliases.insert("statux".to_string(), "status -sb".to_string());
+        let rule = rule_with_aliases(aliases);
+
+        let cmd = Command::new(
+            "git statu",
+            Some("git: 'statu' is not a git command. See 'git --help'.".to_string()),
+        );
+        let result = rule.get_new_command(&cmd);
+
+        assert_eq!(result, vec!["git statux", "git status -sb"]);
+    }
+
+    #[test]
+    fn test_git_alias_expand_plain_and_shell_out_forms() {
+        assert_eq!(GitAliasRule::expand("status -sb"), Some("status -sb"));
+        assert_eq!(
+            GitAliasRule::expand("!git log --oneline"),
+            Some("log --oneline")
+        );
+        assert_eq!(GitAliasRule::expand("!gitk --all"), None);
+    }
+
+    #[test]
+    fn test_git_alias_skips_expansion_for_non_git_shell_out() {
+        let mut aliases = HashMap::new();
+        aliases.insert("statux".to_string(), "!gitk --all".to_string());
+        let rule = rule_with_aliases(aliases);
+
+        let cmd = Command::new(
+            "git statu",
+            Some("git: 'statu' is not a git command. See 'git --help'.".to_string()),
+        );
+        let result = rule.get_new_command(&cmd);
+
+        assert_eq!(result, vec!["git statux"]);
+    }
+}
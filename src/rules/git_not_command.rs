@@ -1,6 +1,17 @@
+use crate::similarity;
 use crate::types::{Command, Rule};
 use regex::Regex;
 
+/// Known git subcommands, used as a Levenshtein fallback when git's own
+/// error output doesn't include a "did you mean" suggestion (e.g. very old
+/// git versions). Not exhaustive, but covers what users mistype most.
+const GIT_SUBCOMMANDS: &[&str] = &[
+    "add", "branch", "checkout", "cherry-pick", "clone", "commit", "config",
+    "diff", "fetch", "grep", "init", "log", "merge", "mv", "pull", "push",
+    "rebase", "reflog", "remote", "reset", "restore", "revert", "rm", "show",
+    "stash", "status", "submodule", "switch", "tag",
+];
+
 /// Rule that fixes misspelled git commands.
 ///
 /// When you type `git psuh` instead of `git push`, git suggests
@@ -83,15 +94,24 @@ impl Rule for GitNotCommandRule {
         let mut cmd = command.clone();
         let parts = cmd.script_parts();
 
-        if parts.len() >= 2 {
-            let misspelled = &parts[1];
-            suggestions
-                .into_iter()
-                .map(|correct| command.script.replacen(misspelled, &correct, 1))
-                .collect()
-        } else {
-            vec![]
+        if parts.len() < 2 {
+            return vec![];
+        }
+
+        let misspelled = &parts[1];
+
+        // Git didn't offer any suggestion of its own (e.g. an older version
+        // that predates "the most similar command is") - fall back to
+        // Levenshtein distance over known subcommands.
+        if suggestions.is_empty() {
+            let candidates: Vec<String> = GIT_SUBCOMMANDS.iter().map(|s| s.to_string()).collect();
+            suggestions = similarity::closest(misspelled, &candidates);
         }
+
+        suggestions
+            .into_iter()
+            .map(|correct| command.script.replacen(misspelled, &correct, 1))
+            .collect()
     }
 }
 
@@ -142,4 +162,16 @@ The most similar command is
         let new_commands = rule.get_new_command(&cmd);
         assert_eq!(new_commands, vec!["git push origin main"]);
     }
+
+    #[test]
+    fn test_git_not_command_falls_back_to_levenshtein() {
+        let rule = GitNotCommandRule::new();
+        // No "did you mean"/"most similar command" in the output at all.
+        let cmd = Command::new(
+            "git statu",
+            Some("git: 'statu' is not a git command. See 'git --help'.".to_string()),
+        );
+        let new_commands = rule.get_new_command(&cmd);
+        assert_eq!(new_commands, vec!["git status"]);
+    }
 }
@@ -1,3 +1,4 @@
+use crate::git_info::{self, BranchInfo};
 use crate::types::{Command, Rule};
 use regex::Regex;
 
@@ -6,13 +7,31 @@ use regex::Regex;
 /// When you try to `git push` without setting upstream, git suggests:
 /// `git push --set-upstream origin <branch>`
 ///
-/// This rule extracts that suggestion and uses it.
+/// Where possible this resolves the branch and remote directly via
+/// [`crate::git_info`] rather than trusting that suggestion text, since
+/// it's only accurate for git's default (English) locale; the regex
+/// extraction below is kept as a fallback for when no repository is
+/// discoverable.
 pub struct GitPushRule;
 
 impl GitPushRule {
     pub fn new() -> Self {
         Self
     }
+
+    /// Falls back to extracting git's suggested `--set-upstream` invocation
+    /// from its stderr, for when no repository is discoverable via
+    /// [`crate::git_info`] (e.g. it's only readable through the shell that
+    /// produced `output`, not the process running this rule).
+    fn suggestion_from_output(command: &Command) -> Option<String> {
+        let output = command.output.as_ref()?;
+
+        // Format: "git push --set-upstream origin <branch>"
+        let re = Regex::new(r"git push (--set-upstream\s+\S+\s+\S+)").unwrap();
+        let caps = re.captures(output)?;
+
+        Some(caps.get(1)?.as_str().to_string())
+    }
 }
 
 impl Default for GitPushRule {
@@ -48,37 +67,44 @@ impl Rule for GitPushRule {
         output.contains("git push --set-upstream")
     }
 
-    fn get_new_command(&self, command: &Command) -> Vec<String> {
-        let output = match &command.output {
-            Some(out) => out,
-            None => return vec![],
-        };
-
-        // Extract the suggested command from git's output
-        // Format: "git push --set-upstream origin <branch>"
-        let re = Regex::new(r"git push (--set-upstream\s+\S+\s+\S+)").unwrap();
+    fn output_triggers(&self) -> Vec<&str> {
+        vec!["git push --set-upstream"]
+    }
 
-        if let Some(caps) = re.captures(output) {
-            let suggestion = caps.get(1).map(|m| m.as_str()).unwrap_or("");
+    fn script_triggers(&self) -> Vec<&str> {
+        vec!["push"]
+    }
 
-            // Build the new command
-            // If the original command had extra flags, we need to handle them
-            let mut cmd = command.clone();
-            let parts = cmd.script_parts();
+    fn get_new_command(&self, command: &Command) -> Vec<String> {
+        Self::build_new_command(command, git_info::current_branch_info())
+    }
+}
 
-            // Find where 'push' is in the command
-            let push_idx = parts.iter().position(|p| p == "push").unwrap_or(0);
+impl GitPushRule {
+    /// Builds the corrected command from an already-resolved `branch_info`
+    /// (or falls back to parsing `output` when `None`), kept separate from
+    /// the `Rule` entry point so tests can exercise both paths without
+    /// depending on the repository the test binary happens to run in.
+    fn build_new_command(command: &Command, branch_info: Option<BranchInfo>) -> Vec<String> {
+        let mut cmd = command.clone();
+        let parts = cmd.script_parts();
+        let push_idx = match parts.iter().position(|p| p == "push") {
+            Some(idx) => idx,
+            None => return vec![],
+        };
 
-            // Rebuild with the suggestion
-            let mut new_parts: Vec<&str> = parts[..=push_idx].iter().map(|s| s.as_str()).collect();
-            new_parts.push(suggestion);
+        let suggestion = match branch_info {
+            Some(info) => format!("--set-upstream {} {}", info.remote, info.branch),
+            None => match Self::suggestion_from_output(command) {
+                Some(suggestion) => suggestion,
+                None => return vec![],
+            },
+        };
 
-            // Join and return
-            let new_command = new_parts.join(" ");
-            return vec![new_command];
-        }
+        let mut new_parts: Vec<&str> = parts[..=push_idx].iter().map(|s| s.as_str()).collect();
+        new_parts.push(&suggestion);
 
-        vec![]
+        vec![new_parts.join(" ")]
     }
 }
 
@@ -124,13 +150,26 @@ To push the current branch and set the remote as upstream, use
     }
 
     #[test]
-    fn test_git_push_get_new_command() {
-        let rule = GitPushRule::new();
+    fn test_git_push_get_new_command_falls_back_to_output_parsing() {
         let cmd = Command::new("git push", Some(GIT_PUSH_OUTPUT.to_string()));
-        let new_commands = rule.get_new_command(&cmd);
+        let new_commands = GitPushRule::build_new_command(&cmd, None);
         assert_eq!(
             new_commands,
             vec!["git push --set-upstream origin feature-branch"]
         );
     }
+
+    #[test]
+    fn test_git_push_get_new_command_prefers_resolved_branch_info() {
+        let cmd = Command::new("git push", Some(GIT_PUSH_OUTPUT.to_string()));
+        let branch_info = BranchInfo {
+            branch: "main".to_string(),
+            remote: "upstream".to_string(),
+        };
+        let new_commands = GitPushRule::build_new_command(&cmd, Some(branch_info));
+        assert_eq!(
+            new_commands,
+            vec!["git push --set-upstream upstream main"]
+        );
+    }
 }
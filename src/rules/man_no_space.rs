@@ -46,6 +46,14 @@ impl Rule for ManNoSpaceRule {
     fn requires_output(&self) -> bool {
         true
     }
+
+    fn output_triggers(&self) -> Vec<&str> {
+        vec!["command not found"]
+    }
+
+    fn script_triggers(&self) -> Vec<&str> {
+        vec!["man"]
+    }
 }
 
 #[cfg(test)]
@@ -38,6 +38,14 @@ impl Rule for MkdirPRule {
         let result = re.replace(&command.script, "mkdir -p ").to_string();
         vec![result]
     }
+
+    fn output_triggers(&self) -> Vec<&str> {
+        vec!["No such file or directory"]
+    }
+
+    fn script_triggers(&self) -> Vec<&str> {
+        vec!["mkdir"]
+    }
 }
 
 #[cfg(test)]
@@ -10,6 +10,7 @@
 //! Each rule implements the `Rule` trait and is registered
 //! with the rule registry.
 
+use crate::config::Settings;
 use crate::types::Rule;
 
 // Rule modules
@@ -20,6 +21,7 @@ pub mod cd_parent;
 pub mod chmod_x;
 pub mod cp_omitting_directory;
 pub mod git_add;
+pub mod git_alias;
 pub mod git_not_command;
 pub mod git_push;
 pub mod git_stash;
@@ -41,6 +43,7 @@ pub use cd_parent::CdParentRule;
 pub use chmod_x::ChmodXRule;
 pub use cp_omitting_directory::CpOmittingDirectoryRule;
 pub use git_add::GitAddRule;
+pub use git_alias::GitAliasRule;
 pub use git_not_command::GitNotCommandRule;
 pub use git_push::GitPushRule;
 pub use git_stash::GitStashRule;
@@ -58,8 +61,9 @@ pub use touch::TouchRule;
 ///
 /// This function returns a vector of boxed rule trait objects.
 /// Rules are returned in no particular order - sorting by priority
-/// is done by the Corrector.
-pub fn get_builtin_rules() -> Vec<Box<dyn Rule>> {
+/// is done by the Corrector. `settings` configures rules that have
+/// tunable behavior, such as `NoCommandRule`'s similarity metric.
+pub fn get_builtin_rules(settings: &Settings) -> Vec<Box<dyn Rule>> {
     let rules: Vec<Box<dyn Rule>> = vec![
         // Permission rules
         Box::new(SudoRule::new()),
@@ -76,6 +80,7 @@ pub fn get_builtin_rules() -> Vec<Box<dyn Rule>> {
         // Git rules
         Box::new(GitPushRule::new()),
         Box::new(GitNotCommandRule::new()),
+        Box::new(GitAliasRule::new()),
         Box::new(GitAddRule::new()),
         Box::new(GitStashRule::new()),
         // Cargo (Rust) rules
@@ -83,7 +88,7 @@ pub fn get_builtin_rules() -> Vec<Box<dyn Rule>> {
         // Python rules
         Box::new(PythonCommandRule::new()),
         // Command rules
-        Box::new(NoCommandRule::new()),
+        Box::new(NoCommandRule::with_settings(settings)),
         Box::new(ManNoSpaceRule::new()),
         Box::new(OpenRule::new()),
         // Misc rules
@@ -157,13 +162,13 @@ mod tests {
 
     #[test]
     fn test_get_builtin_rules() {
-        let rules = get_builtin_rules();
-        assert_eq!(rules.len(), 19);
+        let rules = get_builtin_rules(&Settings::default());
+        assert_eq!(rules.len(), 20);
     }
 
     #[test]
     fn test_builtin_rules_have_names() {
-        let rules = get_builtin_rules();
+        let rules = get_builtin_rules(&Settings::default());
         let names: Vec<&str> = rules.iter().map(|r| r.name()).collect();
 
         // Original 10 rules
@@ -1,113 +1,56 @@
+use crate::config::Settings;
+use crate::executable_index;
+use crate::shell;
+use crate::similarity::{self, SimilarityMetric};
 use crate::types::{Command, Rule};
-use std::collections::HashSet;
-use std::env;
-use std::fs;
-use std::path::Path;
-use strsim::jaro_winkler;
 
 /// Rule that suggests similar commands when a command is not found.
 ///
 /// When you mistype a command like `gti` instead of `git`, this rule
-/// suggests similar executables that exist on the system.
+/// suggests similar executables that exist on the system, sourced from the
+/// process-wide [`executable_index`] rather than re-scanning `PATH` itself,
+/// plus the current shell's alias names (the same `TF_SHELL_ALIASES` map
+/// each [`crate::shell::ShellOperations`] impl already parses for
+/// `expand_aliases`) so a mistyped alias like `gs` is suggested too. Ranking
+/// is delegated to [`crate::similarity::closest_ranked`] with a metric and
+/// cutoff configurable via `Settings::similarity_metric`/
+/// `Settings::similarity_threshold`.
 pub struct NoCommandRule {
-    /// Cached list of executables (computed lazily)
-    executables_cache: Option<HashSet<String>>,
+    metric: SimilarityMetric,
+    threshold: f64,
+    alias_names: Vec<String>,
 }
 
 impl NoCommandRule {
     pub fn new() -> Self {
-        Self {
-            executables_cache: None,
-        }
+        Self::with_settings(&Settings::default())
     }
 
-    /// Gets all executables from PATH.
-    fn get_all_executables(&mut self) -> &HashSet<String> {
-        if self.executables_cache.is_none() {
-            let mut executables = HashSet::new();
-
-            if let Ok(path_var) = env::var("PATH") {
-                for path_dir in env::split_paths(&path_var) {
-                    if let Ok(entries) = fs::read_dir(&path_dir) {
-                        for entry in entries.filter_map(|e| e.ok()) {
-                            let path = entry.path();
-                            if Self::is_executable(&path) {
-                                if let Some(name) = path.file_name() {
-                                    if let Some(name_str) = name.to_str() {
-                                        executables.insert(name_str.to_string());
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-
-            self.executables_cache = Some(executables);
-        }
-
-        self.executables_cache.as_ref().unwrap()
-    }
-
-    /// Checks if a path is executable.
-    #[cfg(unix)]
-    fn is_executable(path: &Path) -> bool {
-        use std::os::unix::fs::PermissionsExt;
-        if let Ok(metadata) = fs::metadata(path) {
-            let permissions = metadata.permissions();
-            return metadata.is_file() && (permissions.mode() & 0o111 != 0);
+    /// Creates a rule that ranks suggestions using `settings`'s configured
+    /// similarity metric and threshold.
+    pub fn with_settings(settings: &Settings) -> Self {
+        Self {
+            metric: settings.similarity_metric,
+            threshold: settings.similarity_threshold,
+            alias_names: Self::shell_alias_names(settings),
         }
-        false
     }
 
-    #[cfg(not(unix))]
-    fn is_executable(path: &Path) -> bool {
-        // On Windows, check for common executable extensions
-        if let Some(ext) = path.extension() {
-            let ext_lower = ext.to_string_lossy().to_lowercase();
-            return matches!(ext_lower.as_str(), "exe" | "cmd" | "bat" | "com");
-        }
-        false
+    /// Names of the current shell's aliases, so a mistyped alias can be
+    /// suggested alongside mistyped executables. Empty if the shell can't
+    /// be detected (e.g. running outside any recognized shell).
+    fn shell_alias_names(settings: &Settings) -> Vec<String> {
+        shell::get_current_shell(settings.clone())
+            .map(|shell| shell.shell_config().aliases.keys().cloned().collect())
+            .unwrap_or_default()
     }
 
-    /// Finds close matches for a command name.
-    fn get_close_matches(&mut self, name: &str, max_matches: usize) -> Vec<String> {
-        let executables = self.get_all_executables();
-        // Use lower threshold for short names (jaro_winkler gives lower scores for short strings)
-        let threshold = if name.len() <= 3 {
-            0.5
-        } else if name.len() <= 5 {
-            0.6
-        } else {
-            0.7
-        };
-
-        let mut matches: Vec<(String, f64)> = executables
-            .iter()
-            .filter_map(|exec| {
-                let similarity = jaro_winkler(name, exec);
-                if similarity >= threshold {
-                    Some((exec.clone(), similarity))
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        // Sort by similarity (highest first)
-        matches.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-
-        // Return top matches
-        matches
-            .into_iter()
-            .take(max_matches)
-            .map(|(name, _)| name)
-            .collect()
-    }
-
-    /// Checks if a command exists.
-    fn command_exists(&mut self, name: &str) -> bool {
-        self.get_all_executables().contains(name)
+    /// Finds close matches for a command name, ranked by the configured
+    /// similarity metric.
+    fn get_close_matches(&self, name: &str, max_matches: usize) -> Vec<String> {
+        let mut candidates: Vec<String> = executable_index::executables().iter().cloned().collect();
+        candidates.extend(self.alias_names.iter().cloned());
+        similarity::closest_ranked(name, &candidates, self.metric, self.threshold, max_matches)
     }
 }
 
@@ -148,8 +91,7 @@ impl Rule for NoCommandRule {
         }
 
         // Check that the command doesn't exist but we can find similar ones
-        let mut rule = NoCommandRule::new();
-        !rule.command_exists(cmd_name) && !rule.get_close_matches(cmd_name, 1).is_empty()
+        !executable_index::exists(cmd_name) && !self.get_close_matches(cmd_name, 1).is_empty()
     }
 
     fn get_new_command(&self, command: &Command) -> Vec<String> {
@@ -161,8 +103,7 @@ impl Rule for NoCommandRule {
         }
 
         let old_command = &parts[0];
-        let mut rule = NoCommandRule::new();
-        let matches = rule.get_close_matches(old_command, 3);
+        let matches = self.get_close_matches(old_command, 3);
 
         matches
             .into_iter()
@@ -197,39 +138,36 @@ mod tests {
     }
 
     #[test]
-    fn test_no_command_close_matches() {
-        // Test that the jaro_winkler similarity algorithm works as expected
-        // "gti" is a typo of "git" - jaro_winkler gives ~0.55
-        let similarity = jaro_winkler("git", "gti");
-        assert!(
-            similarity > 0.5,
-            "gti should be similar to git, got {}",
-            similarity
-        );
-
-        // "git" and "xyz" should not be similar
-        let similarity2 = jaro_winkler("git", "xyz");
-        assert!(
-            similarity2 < 0.5,
-            "xyz should not be similar to git, got {}",
-            similarity2
-        );
+    fn test_no_command_close_matches_respects_max() {
+        // Environment-dependent (depends on what's on PATH), but max_matches
+        // should always cap the result; the matching algorithm itself is
+        // covered by crate::similarity's own tests.
+        let rule = NoCommandRule::new();
+        let matches = rule.get_close_matches("buld", 1);
+        assert!(matches.len() <= 1);
+    }
 
-        // "push" and "psuh" should be similar (transposition)
-        let similarity3 = jaro_winkler("push", "psuh");
-        assert!(
-            similarity3 > 0.7,
-            "psuh should be similar to push, got {}",
-            similarity3
-        );
+    #[test]
+    fn test_no_command_close_matches_includes_alias_names() {
+        let rule = NoCommandRule {
+            metric: SimilarityMetric::Levenshtein,
+            threshold: 0.5,
+            alias_names: vec!["gco".to_string()],
+        };
+        let matches = rule.get_close_matches("gc", 3);
+        assert!(matches.contains(&"gco".to_string()));
+    }
 
-        // "mkdir" and "mkidr" should be similar
-        let similarity4 = jaro_winkler("mkdir", "mkidr");
-        assert!(
-            similarity4 > 0.8,
-            "mkidr should be similar to mkdir, got {}",
-            similarity4
-        );
+    #[test]
+    fn test_no_command_with_settings_uses_configured_metric() {
+        let settings = Settings {
+            similarity_metric: SimilarityMetric::Levenshtein,
+            similarity_threshold: 0.9,
+            ..Settings::default()
+        };
+        let rule = NoCommandRule::with_settings(&settings);
+        assert_eq!(rule.metric, SimilarityMetric::Levenshtein);
+        assert_eq!(rule.threshold, 0.9);
     }
 
     #[test]
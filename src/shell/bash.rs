@@ -1,4 +1,5 @@
-use super::{ShellOperations, ShellType};
+use super::history::parse_bash_history;
+use super::{ShellConfig, ShellOperations, ShellType};
 use crate::config::Settings;
 use crate::error::Result;
 use std::collections::HashMap;
@@ -10,11 +11,13 @@ use std::path::PathBuf;
 /// Bash shell implementation.
 pub struct Bash {
     settings: Settings,
+    config: ShellConfig,
 }
 
 impl Bash {
     pub fn new(settings: Settings) -> Self {
-        Self { settings }
+        let config = ShellConfig::new(env::vars().collect(), Self::get_aliases_from_env());
+        Self { settings, config }
     }
 
     /// Parses an alias line from bash alias output.
@@ -101,31 +104,20 @@ impl ShellOperations for Bash {
 
         let file = fs::File::open(&history_file)?;
         let reader = BufReader::new(file);
-        let lines: Vec<String> = reader
-            .lines()
-            .filter_map(|l| l.ok())
+        let lines: Vec<String> = reader.lines().map_while(|l| l.ok()).collect();
+        let commands: Vec<String> = parse_bash_history(lines)
+            .into_iter()
+            .map(|entry| entry.command)
             .filter(|l| !l.is_empty())
             .collect();
 
-        // Return last `limit` lines
-        let start = lines.len().saturating_sub(limit);
-        Ok(lines[start..].to_vec())
+        // Return last `limit` commands
+        let start = commands.len().saturating_sub(limit);
+        Ok(commands[start..].to_vec())
     }
 
-    fn expand_aliases(&self, command: &str) -> String {
-        let aliases = Self::get_aliases_from_env();
-        let parts: Vec<&str> = command.splitn(2, ' ').collect();
-        let binary = parts[0];
-
-        if let Some(expanded) = aliases.get(binary) {
-            if parts.len() > 1 {
-                format!("{} {}", expanded, parts[1])
-            } else {
-                expanded.clone()
-            }
-        } else {
-            command.to_string()
-        }
+    fn shell_config(&self) -> &ShellConfig {
+        &self.config
     }
 
     fn put_to_history(&self, command: &str) -> Result<()> {
@@ -10,17 +10,32 @@ pub enum ShellType {
     Tcsh,
     PowerShell,
     Cmd,
+    Nushell,
+    Xonsh,
+    Elvish,
+    /// A shell `from_name`/`from_path` didn't recognize, kept alive via
+    /// [`Shell::custom`] instead of collapsing to [`ShellType::Unknown`].
+    /// The calling convention it should be treated as is recorded
+    /// separately on [`Shell::emulates`].
+    Custom,
     Unknown,
 }
 
 impl ShellType {
     /// Returns the shell type from a shell name string.
+    ///
+    /// Handles a leading `-` (the login-shell prefix reported as e.g.
+    /// `-zsh`), a path prefix (only the basename is matched), and a
+    /// trailing version suffix (`bash-5.2`). Does not resolve symlinks -
+    /// use [`ShellType::from_path`] for that.
     pub fn from_name(name: &str) -> Self {
         let name_lower = name.to_lowercase();
         let basename = std::path::Path::new(&name_lower)
             .file_name()
             .and_then(|s| s.to_str())
             .unwrap_or(&name_lower);
+        let basename = strip_login_prefix(basename);
+        let basename = strip_version_suffix(basename);
 
         match basename {
             "bash" | "bash.exe" => ShellType::Bash,
@@ -29,10 +44,35 @@ impl ShellType {
             "tcsh" | "csh" => ShellType::Tcsh,
             "powershell" | "powershell.exe" | "pwsh" | "pwsh.exe" => ShellType::PowerShell,
             "cmd" | "cmd.exe" => ShellType::Cmd,
+            "nu" | "nu.exe" | "nushell" | "nushell.exe" => ShellType::Nushell,
+            "xonsh" | "xonsh.exe" => ShellType::Xonsh,
+            "elvish" | "elvish.exe" => ShellType::Elvish,
             _ => ShellType::Unknown,
         }
     }
 
+    /// Resolves a shell executable path to a [`ShellType`], canonicalizing
+    /// symlinks first so e.g. `/bin/sh -> bash` is detected as the real
+    /// underlying shell rather than falling through on `sh`. Falls back to
+    /// matching the path's own basename via [`ShellType::from_name`] when
+    /// canonicalization fails (path doesn't exist, isn't a symlink, or
+    /// resolves to something `from_name` still doesn't recognize).
+    pub fn from_path(path: &std::path::Path) -> Self {
+        if let Ok(resolved) = std::fs::canonicalize(path) {
+            let shell_type = resolved
+                .to_str()
+                .map(ShellType::from_name)
+                .unwrap_or(ShellType::Unknown);
+            if shell_type != ShellType::Unknown {
+                return shell_type;
+            }
+        }
+
+        path.to_str()
+            .map(ShellType::from_name)
+            .unwrap_or(ShellType::Unknown)
+    }
+
     /// Returns the display name of the shell.
     pub fn name(&self) -> &'static str {
         match self {
@@ -42,9 +82,24 @@ impl ShellType {
             ShellType::Tcsh => "tcsh",
             ShellType::PowerShell => "powershell",
             ShellType::Cmd => "cmd",
+            ShellType::Nushell => "nushell",
+            ShellType::Xonsh => "xonsh",
+            ShellType::Elvish => "elvish",
+            ShellType::Custom => "custom",
             ShellType::Unknown => "unknown",
         }
     }
+
+    /// Returns `true` if this shell follows POSIX-family env-var/quoting
+    /// conventions (`export FOO=bar`, `sh -c`-style invocation) closely
+    /// enough that command-reconstruction and alias-emitting code can
+    /// treat it like bash/zsh/fish. `false` for shells with their own
+    /// syntax - csh/tcsh (`setenv`), PowerShell/cmd, and the modern
+    /// non-POSIX shells Nushell (`let-env`), Xonsh (Python-style
+    /// assignment), and Elvish.
+    pub fn is_posix_like(&self) -> bool {
+        matches!(self, ShellType::Bash | ShellType::Zsh | ShellType::Fish)
+    }
 }
 
 impl std::fmt::Display for ShellType {
@@ -58,6 +113,10 @@ impl std::fmt::Display for ShellType {
 pub struct Shell {
     pub shell_type: ShellType,
     pub path: Option<String>,
+    /// For [`ShellType::Custom`], the known shell whose calling conventions
+    /// (env-var syntax, `-c`/`/C`/`-Command` invocation) this shell should
+    /// be treated as emulating. `None` for every other `shell_type`.
+    pub emulates: Option<ShellType>,
 }
 
 impl Shell {
@@ -65,6 +124,7 @@ impl Shell {
         Self {
             shell_type,
             path: None,
+            emulates: None,
         }
     }
 
@@ -72,6 +132,19 @@ impl Shell {
         Self {
             shell_type,
             path: Some(path),
+            emulates: None,
+        }
+    }
+
+    /// Builds a [`ShellType::Custom`] shell for an executable `from_name`
+    /// didn't recognize, recording which known shell's calling conventions
+    /// (`emulates`) it should be treated as following - POSIX `-c` style,
+    /// cmd's `/C`, or PowerShell's `-Command`.
+    pub fn custom(path: String, emulates: ShellType) -> Self {
+        Self {
+            shell_type: ShellType::Custom,
+            path: Some(path),
+            emulates: Some(emulates),
         }
     }
 }
@@ -81,19 +154,29 @@ impl Shell {
 /// Detection priority:
 /// 1. TF_SHELL environment variable (set by our alias)
 /// 2. SHELL environment variable
-/// 3. Process tree walking (TODO: implement with sysinfo crate)
+/// 3. COMSPEC environment variable (Windows cmd)
+/// 4. PSModulePath environment variable (Windows PowerShell)
+/// 5. Walking the process tree for the nearest recognized parent shell
+/// 6. Defaulting to PowerShell on Windows when none of the above found a
+///    shell
 pub fn detect_shell() -> Result<Shell> {
     // 1. Check TF_SHELL (set by our shell alias)
     if let Ok(tf_shell) = env::var("TF_SHELL") {
-        let shell_type = ShellType::from_name(&tf_shell);
+        let shell_type = resolve_shell_type(&tf_shell);
         if shell_type != ShellType::Unknown {
             return Ok(Shell::with_path(shell_type, tf_shell));
         }
+        // An unrecognized basename is still useful if it's an absolute path:
+        // treat it as a custom shell rather than giving up on it.
+        if std::path::Path::new(&tf_shell).is_absolute() {
+            let emulates = guess_calling_convention(&tf_shell);
+            return Ok(Shell::custom(tf_shell, emulates));
+        }
     }
 
     // 2. Check SHELL environment variable (Unix)
     if let Ok(shell_path) = env::var("SHELL") {
-        let shell_type = ShellType::from_name(&shell_path);
+        let shell_type = resolve_shell_type(&shell_path);
         if shell_type != ShellType::Unknown {
             return Ok(Shell::with_path(shell_type, shell_path));
         }
@@ -111,11 +194,96 @@ pub fn detect_shell() -> Result<Shell> {
         return Ok(Shell::new(ShellType::PowerShell));
     }
 
-    // TODO: Walk process tree to find parent shell
+    // 5. Walk the process tree - covers subshells, non-login shells, or
+    // anything else spawned without any of the env vars above set.
+    if let Some(shell) = detect_shell_from_process_tree() {
+        return Ok(shell);
+    }
+
+    // 6. Windows without SHELL set (cmd.exe's own default console host) has
+    // no further env var to check; PowerShell is the modern default.
+    if cfg!(windows) {
+        return Ok(Shell::new(ShellType::PowerShell));
+    }
 
     Err(TheFuckError::ShellDetectionFailed)
 }
 
+/// Resolves a value that may be either a bare shell name or a path to one,
+/// using [`ShellType::from_path`]'s symlink canonicalization when it looks
+/// like a path and [`ShellType::from_name`] otherwise.
+fn resolve_shell_type(value: &str) -> ShellType {
+    let path = std::path::Path::new(value);
+    if path.is_absolute() {
+        ShellType::from_path(path)
+    } else {
+        ShellType::from_name(value)
+    }
+}
+
+/// Walks upward from the current process through parent PIDs via
+/// [`sysinfo`], returning the first parent whose executable
+/// [`ShellType::from_path`] (or, lacking an executable path, whose process
+/// name [`ShellType::from_name`]) recognizes. Stops at the first match, at
+/// PID 1, or once a process has no further parent.
+fn detect_shell_from_process_tree() -> Option<Shell> {
+    let system = sysinfo::System::new_all();
+    let mut pid = sysinfo::get_current_pid().ok()?;
+
+    loop {
+        let parent_pid = system.process(pid)?.parent()?;
+        let parent = system.process(parent_pid)?;
+        let exe = parent.exe();
+        let shell_type = match exe {
+            Some(exe) => ShellType::from_path(exe),
+            None => ShellType::from_name(&parent.name().to_string_lossy()),
+        };
+
+        if shell_type != ShellType::Unknown {
+            let path = exe
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| parent.name().to_string_lossy().into_owned());
+            return Some(Shell::with_path(shell_type, path));
+        }
+
+        if parent_pid.as_u32() <= 1 {
+            return None;
+        }
+        pid = parent_pid;
+    }
+}
+
+/// Strips the leading `-` a login shell's process name is reported with
+/// (e.g. `-zsh`), so it still matches [`ShellType::from_name`].
+fn strip_login_prefix(name: &str) -> &str {
+    name.strip_prefix('-').unwrap_or(name)
+}
+
+/// Strips a trailing version suffix like the `-5.2` in `bash-5.2`, so a
+/// versioned executable name still matches its base shell's aliases.
+fn strip_version_suffix(basename: &str) -> &str {
+    match basename.split_once('-') {
+        Some((base, suffix)) if suffix.starts_with(|c: char| c.is_ascii_digit()) => base,
+        _ => basename,
+    }
+}
+
+/// Picks the calling convention an unrecognized `TF_SHELL` path should
+/// emulate, based on substrings in the path itself (e.g. a
+/// `my-company-shell` wrapper around `cmd.exe` still has "cmd" in its
+/// path). Defaults to POSIX (`ShellType::Bash`'s `-c` convention), the
+/// most common shape for niche and wrapper shells.
+fn guess_calling_convention(path: &str) -> ShellType {
+    let path_lower = path.to_lowercase();
+    if path_lower.contains("powershell") || path_lower.contains("pwsh") {
+        ShellType::PowerShell
+    } else if path_lower.contains("cmd") {
+        ShellType::Cmd
+    } else {
+        ShellType::Bash
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,9 +298,114 @@ mod tests {
         assert_eq!(ShellType::from_name("unknown_shell"), ShellType::Unknown);
     }
 
+    #[test]
+    fn test_shell_type_from_name_login_prefix() {
+        assert_eq!(ShellType::from_name("-bash"), ShellType::Bash);
+        assert_eq!(ShellType::from_name("-zsh"), ShellType::Zsh);
+    }
+
+    #[test]
+    fn test_shell_type_from_name_versioned() {
+        assert_eq!(ShellType::from_name("bash-5.2"), ShellType::Bash);
+        assert_eq!(ShellType::from_name("/usr/bin/zsh-5.9"), ShellType::Zsh);
+    }
+
+    #[test]
+    fn test_shell_type_name_round_trips_through_from_name() {
+        // `TF_SHELL` is set to `name()`'s output by the aliases ShellGenerator
+        // emits, so detect_shell's `TF_SHELL` re-check must recognize it.
+        for shell_type in [
+            ShellType::Bash,
+            ShellType::Zsh,
+            ShellType::Fish,
+            ShellType::Tcsh,
+            ShellType::PowerShell,
+            ShellType::Cmd,
+            ShellType::Nushell,
+            ShellType::Xonsh,
+            ShellType::Elvish,
+        ] {
+            assert_eq!(
+                ShellType::from_name(shell_type.name()),
+                shell_type,
+                "{} did not round-trip",
+                shell_type.name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_strip_version_suffix() {
+        assert_eq!(strip_version_suffix("bash-5.2"), "bash");
+        assert_eq!(strip_version_suffix("bash"), "bash");
+        assert_eq!(strip_version_suffix("my-wrapper"), "my-wrapper");
+    }
+
+    #[test]
+    fn test_shell_type_from_path_nonexistent_falls_back_to_basename() {
+        assert_eq!(
+            ShellType::from_path(std::path::Path::new("/no/such/path/bash")),
+            ShellType::Bash
+        );
+    }
+
+    #[test]
+    fn test_shell_type_from_path_resolves_symlink() {
+        let dir =
+            std::env::temp_dir().join(format!("thefuck-test-from-path-{}", std::process::id()));
+        let _ = std::fs::create_dir_all(&dir);
+        let target = dir.join("bash");
+        std::fs::write(&target, "").unwrap();
+        let link = dir.join("sh");
+        #[cfg(unix)]
+        {
+            let _ = std::fs::remove_file(&link);
+            std::os::unix::fs::symlink(&target, &link).unwrap();
+            assert_eq!(ShellType::from_path(&link), ShellType::Bash);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_shell_type_display() {
         assert_eq!(format!("{}", ShellType::Bash), "bash");
         assert_eq!(format!("{}", ShellType::Zsh), "zsh");
     }
+
+    #[test]
+    fn test_strip_login_prefix() {
+        assert_eq!(strip_login_prefix("-zsh"), "zsh");
+        assert_eq!(strip_login_prefix("zsh"), "zsh");
+    }
+
+    #[test]
+    fn test_detect_shell_from_process_tree_does_not_panic() {
+        // Environment-dependent (whatever spawned the test binary), but it
+        // should never panic and should terminate rather than looping.
+        let _ = detect_shell_from_process_tree();
+    }
+
+    #[test]
+    fn test_shell_custom() {
+        let shell = Shell::custom("/opt/wrapper-shell".to_string(), ShellType::Bash);
+        assert_eq!(shell.shell_type, ShellType::Custom);
+        assert_eq!(shell.path.as_deref(), Some("/opt/wrapper-shell"));
+        assert_eq!(shell.emulates, Some(ShellType::Bash));
+    }
+
+    #[test]
+    fn test_guess_calling_convention() {
+        assert_eq!(
+            guess_calling_convention("/usr/bin/my-wrapper"),
+            ShellType::Bash
+        );
+        assert_eq!(
+            guess_calling_convention(r"C:\tools\my-cmd-wrapper.exe"),
+            ShellType::Cmd
+        );
+        assert_eq!(
+            guess_calling_convention(r"C:\tools\pwsh-wrapper.exe"),
+            ShellType::PowerShell
+        );
+    }
 }
@@ -1,6 +1,8 @@
-use super::{ShellOperations, ShellType};
+use super::{ShellConfig, ShellOperations, ShellType};
 use crate::config::Settings;
 use crate::error::Result;
+use std::collections::HashMap;
+use std::env;
 use std::fs;
 use std::fs::OpenOptions;
 use std::io::{BufRead, BufReader, Write};
@@ -10,11 +12,83 @@ use std::time::{SystemTime, UNIX_EPOCH};
 /// Fish shell implementation.
 pub struct Fish {
     settings: Settings,
+    config: ShellConfig,
 }
 
 impl Fish {
     pub fn new(settings: Settings) -> Self {
-        Self { settings }
+        let config = ShellConfig::new(env::vars().collect(), Self::get_aliases_from_env());
+        Self { settings, config }
+    }
+
+    /// Parses the `functions <name>` output fish dumps into
+    /// `TF_SHELL_ALIASES` (see `app_alias` below) into an alias name ->
+    /// expansion map.
+    ///
+    /// Fish aliases are themselves functions that wrap the aliased command
+    /// and forward arguments via `$argv`:
+    /// ```fish
+    /// function ll --wraps=ls\ -la --description 'alias ll=ls -la'
+    ///     ls -la $argv
+    /// end
+    /// ```
+    /// Only functions whose body is a single line ending in `$argv` are
+    /// treated as aliases; anything with extra logic (like the `fuck`
+    /// wrapper function itself) is left alone, since we can't safely guess
+    /// what re-running it would do.
+    fn parse_functions(text: &str) -> HashMap<String, String> {
+        let mut aliases = HashMap::new();
+        let mut current_name: Option<&str> = None;
+        let mut forwarded_command: Option<String> = None;
+        let mut is_simple_alias = true;
+
+        for line in text.lines() {
+            let trimmed = line.trim();
+
+            if let Some(name) = trimmed
+                .strip_prefix("function ")
+                .and_then(|rest| rest.split_whitespace().next())
+            {
+                current_name = Some(name);
+                forwarded_command = None;
+                is_simple_alias = true;
+                continue;
+            }
+
+            if current_name.is_none() {
+                continue;
+            }
+
+            if trimmed == "end" {
+                if let Some(name) = current_name.take() {
+                    if is_simple_alias {
+                        if let Some(command) = forwarded_command.take() {
+                            aliases.insert(name.to_string(), command);
+                        }
+                    }
+                }
+                continue;
+            }
+
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            match trimmed.strip_suffix("$argv") {
+                Some(prefix) if forwarded_command.is_none() => {
+                    forwarded_command = Some(prefix.trim_end().to_string());
+                }
+                _ => is_simple_alias = false,
+            }
+        }
+
+        aliases
+    }
+
+    /// Gets aliases from the `TF_SHELL_ALIASES` environment variable.
+    fn get_aliases_from_env() -> HashMap<String, String> {
+        let raw = env::var("TF_SHELL_ALIASES").unwrap_or_default();
+        Self::parse_functions(&raw)
     }
 
     /// Gets the history file path.
@@ -61,6 +135,7 @@ impl ShellOperations for Fish {
     set -l fucked_up_command $history[1]
     set -lx TF_SHELL fish
     set -lx TF_ALIAS {name}
+    set -lx TF_SHELL_ALIASES (for f in (functions); functions $f; end | string collect)
     set -lx PYTHONIOENCODING utf-8
     thefuck --force-command "$fucked_up_command" $argv | read -l unfucked_command
     if test -n "$unfucked_command"
@@ -102,11 +177,8 @@ end"#,
         Ok(lines[start..].to_vec())
     }
 
-    fn expand_aliases(&self, command: &str) -> String {
-        // Fish aliases are more complex - they can be functions
-        // For now, we just return the command as-is
-        // TODO: Implement fish function/alias expansion
-        command.to_string()
+    fn shell_config(&self) -> &ShellConfig {
+        &self.config
     }
 
     fn put_to_history(&self, command: &str) -> Result<()> {
@@ -136,6 +208,20 @@ end"#,
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_functions_simple_alias() {
+        let text = "function ll --wraps=ls\\ -la --description 'alias ll=ls -la'\n    ls -la $argv\nend\n";
+        let result = Fish::parse_functions(text);
+        assert_eq!(result.get("ll"), Some(&"ls -la".to_string()));
+    }
+
+    #[test]
+    fn test_parse_functions_ignores_complex_function() {
+        let text = "function fuck\n    set -l cmd (thefuck $argv)\n    eval $cmd\nend\n";
+        let result = Fish::parse_functions(text);
+        assert_eq!(result.get("fuck"), None);
+    }
+
     #[test]
     fn test_script_from_history() {
         let line = "- cmd: git push origin main";
@@ -0,0 +1,145 @@
+use super::ShellType;
+
+/// Produces the shell-specific alias function and instant-mode hook for a
+/// [`ShellType`], independent of whether a full [`super::ShellOperations`]
+/// implementation exists for it yet (PowerShell, cmd, and the shells added
+/// in chunk7-2 have no dedicated struct the way `Bash`/`Zsh`/`Fish` do).
+///
+/// This mirrors `clap_complete`'s `Generator` trait, which produces a
+/// distinct completion script per `Shell`: here the "completion" is the
+/// alias function that exports `TF_SHELL`, keeping it in sync with what
+/// [`super::detect_shell`] recognizes for every shell this crate knows
+/// about, not just the three with full history/quoting support.
+pub trait ShellGenerator {
+    /// Returns the shell function/macro that invokes thefuck as `name`,
+    /// exporting `TF_SHELL` so `detect_shell` recognizes the caller.
+    fn alias(&self, name: &str) -> String;
+
+    /// Returns the per-shell hook instant mode needs to tap into every
+    /// prompt and log output instead of re-running the command, or `None`
+    /// for shells with no such hook (cmd has no prompt-hook mechanism).
+    fn instant_mode_hook(&self) -> Option<String>;
+}
+
+impl ShellGenerator for ShellType {
+    fn alias(&self, name: &str) -> String {
+        match self {
+            ShellType::Fish => format!(
+                r#"function {name}
+    set -l TF_CMD (TF_SHELL=fish TF_ALIAS={name} thefuck --force-command "$history[1]")
+    if test -n "$TF_CMD"
+        eval $TF_CMD
+    end
+end"#,
+                name = name
+            ),
+            ShellType::PowerShell => format!(
+                r#"function {name} {{
+    $env:TF_SHELL = "powershell"
+    $env:TF_ALIAS = "{name}"
+    $TF_CMD = thefuck --force-command (Get-History -Count 1).CommandLine
+    if ($TF_CMD) {{
+        Invoke-Expression $TF_CMD
+    }}
+}}
+Set-Alias {name} {name}"#,
+                name = name
+            ),
+            ShellType::Cmd => format!(
+                r#"doskey {name}=set TF_SHELL=cmd && set TF_ALIAS={name} && for /f "delims=" %i in ('thefuck --force-command "$arg$"') do @%i"#,
+                name = name
+            ),
+            // bash/zsh/tcsh/Nushell/Xonsh/Elvish/Custom/Unknown: a POSIX-ish
+            // function that exports TF_SHELL and eval's thefuck's stdout.
+            // Full support for the non-POSIX shells' own alias syntax lands
+            // once they get a dedicated ShellOperations impl; until then
+            // this keeps `thefuck alias` from erroring out entirely.
+            _ => format!(
+                r#"function {name} () {{
+    export TF_SHELL={shell};
+    export TF_ALIAS={name};
+    TF_CMD=$(thefuck --force-command "$(fc -ln -10)") && eval "$TF_CMD";
+}}"#,
+                name = name,
+                shell = self.name()
+            ),
+        }
+    }
+
+    fn instant_mode_hook(&self) -> Option<String> {
+        match self {
+            ShellType::Bash => Some(
+                r#"export PROMPT_COMMAND='{ history 1 | sed "s/^[ ]*[0-9]*[ ]*//"; } >> ~/.thefuck-instant.log;'"${PROMPT_COMMAND}""#
+                    .to_string(),
+            ),
+            ShellType::Zsh => Some(
+                "precmd_functions+=('print -r -- ${history[$HISTCMD]} >> ~/.thefuck-instant.log')"
+                    .to_string(),
+            ),
+            ShellType::Fish => Some(
+                r#"function __thefuck_instant_mode_log --on-event fish_postexec
+    echo $argv[1] >> ~/.thefuck-instant.log
+end"#
+                    .to_string(),
+            ),
+            // PowerShell, cmd, tcsh, and the non-POSIX shells have no
+            // equivalent prompt-hook wired up yet.
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alias_bash() {
+        let alias = ShellType::Bash.alias("fuck");
+        assert!(alias.contains("function fuck ()"));
+        assert!(alias.contains("export TF_SHELL=bash;"));
+    }
+
+    #[test]
+    fn test_alias_fish() {
+        let alias = ShellType::Fish.alias("fuck");
+        assert!(alias.starts_with("function fuck"));
+        assert!(alias.contains("TF_SHELL=fish"));
+    }
+
+    #[test]
+    fn test_alias_powershell() {
+        let alias = ShellType::PowerShell.alias("fuck");
+        assert!(alias.contains("function fuck {"));
+        assert!(alias.contains(r#"$env:TF_SHELL = "powershell""#));
+    }
+
+    #[test]
+    fn test_alias_cmd() {
+        let alias = ShellType::Cmd.alias("fuck");
+        assert!(alias.starts_with("doskey fuck="));
+        assert!(alias.contains("TF_SHELL=cmd"));
+    }
+
+    #[test]
+    fn test_alias_unsupported_shell_falls_back_to_posix_function() {
+        let alias = ShellType::Nushell.alias("fuck");
+        assert!(alias.contains("function fuck ()"));
+        assert!(alias.contains("export TF_SHELL=nushell;"));
+        // The exported value must be one `ShellType::from_name` maps back
+        // to Nushell, or the alias can't round-trip its own `TF_SHELL`.
+        assert_eq!(ShellType::from_name("nushell"), ShellType::Nushell);
+    }
+
+    #[test]
+    fn test_instant_mode_hook_present_for_posix_shells() {
+        assert!(ShellType::Bash.instant_mode_hook().is_some());
+        assert!(ShellType::Zsh.instant_mode_hook().is_some());
+        assert!(ShellType::Fish.instant_mode_hook().is_some());
+    }
+
+    #[test]
+    fn test_instant_mode_hook_absent_for_cmd() {
+        assert_eq!(ShellType::Cmd.instant_mode_hook(), None);
+    }
+}
@@ -0,0 +1,201 @@
+//! Shared parsing for shell history formats that embed metadata alongside
+//! the command text.
+//!
+//! `zsh` with `EXTENDED_HISTORY` writes `: <start>:<elapsed>;<command>`
+//! lines, and `bash` with `HISTTIMEFORMAT` writes a `#<epoch>` comment line
+//! before each command. Treating either of these as a plain one-line
+//! command leaks timestamps or comment lines into whatever reads history,
+//! so shells parse their file through the functions here instead.
+
+/// A single parsed history entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    /// Unix timestamp the command was run at, if the format recorded one.
+    pub timestamp: Option<i64>,
+    /// The command text, with shell-specific metadata stripped and any
+    /// backslash-continued lines joined with `\n`.
+    pub command: String,
+}
+
+/// Parses zsh's `EXTENDED_HISTORY` format.
+///
+/// Each entry looks like `: <start>:<elapsed>;<command>`; lines without
+/// that prefix are treated as plain commands (covers histories written
+/// without `EXTENDED_HISTORY`). A command ending in `\` continues onto the
+/// next line.
+pub fn parse_zsh_history<I>(lines: I) -> Vec<HistoryEntry>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    collect_entries(lines, |line| match parse_zsh_prefix(line) {
+        Some((timestamp, rest)) => (Some(timestamp), rest),
+        None => (None, line),
+    })
+}
+
+/// Parses bash's `HISTTIMEFORMAT` format.
+///
+/// A `#<epoch>` comment line is attached as the timestamp of the command
+/// line that follows it; lines without a preceding comment have no
+/// timestamp. A command ending in `\` continues onto the next line.
+pub fn parse_bash_history<I>(lines: I) -> Vec<HistoryEntry>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut pending_timestamp = None;
+    collect_entries(lines, move |line| {
+        if let Some(epoch) = line.strip_prefix('#').and_then(|s| s.parse::<i64>().ok()) {
+            pending_timestamp = Some(epoch);
+            return (None, "");
+        }
+        (pending_timestamp.take(), line)
+    })
+}
+
+/// Drives the shared continuation-joining loop, deferring format-specific
+/// prefix stripping to `strip_prefix`. `strip_prefix` returning an empty
+/// string (with no timestamp) means "this line carried no command", e.g. a
+/// standalone bash `#<epoch>` comment line.
+fn collect_entries<I>(
+    lines: I,
+    mut strip_prefix: impl FnMut(&str) -> (Option<i64>, &str),
+) -> Vec<HistoryEntry>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut entries = Vec::new();
+    let mut pending: Option<HistoryEntry> = None;
+
+    for line in lines {
+        let line = line.as_ref();
+
+        if let Some(mut entry) = pending.take() {
+            entry.command.push('\n');
+            entry.command.push_str(line.trim_end_matches('\\'));
+            if line.ends_with('\\') {
+                pending = Some(entry);
+            } else {
+                entries.push(entry);
+            }
+            continue;
+        }
+
+        let (timestamp, rest) = strip_prefix(line);
+        if rest.is_empty() && timestamp.is_none() {
+            continue;
+        }
+
+        let entry = HistoryEntry {
+            timestamp,
+            command: rest.trim_end_matches('\\').to_string(),
+        };
+        if rest.ends_with('\\') {
+            pending = Some(entry);
+        } else {
+            entries.push(entry);
+        }
+    }
+
+    if let Some(entry) = pending {
+        entries.push(entry);
+    }
+
+    entries
+}
+
+fn parse_zsh_prefix(line: &str) -> Option<(i64, &str)> {
+    let rest = line.strip_prefix(": ")?;
+    let (meta, command) = rest.split_once(';')?;
+    let (start, _elapsed) = meta.split_once(':')?;
+    let timestamp = start.trim().parse().ok()?;
+    Some((timestamp, command))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_zsh_history_extended_format() {
+        let lines = [": 1609459200:0;git push origin main"];
+        let entries = parse_zsh_history(lines);
+        assert_eq!(
+            entries,
+            vec![HistoryEntry {
+                timestamp: Some(1609459200),
+                command: "git push origin main".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_zsh_history_plain_line() {
+        let lines = ["git status"];
+        let entries = parse_zsh_history(lines);
+        assert_eq!(
+            entries,
+            vec![HistoryEntry {
+                timestamp: None,
+                command: "git status".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_zsh_history_continuation() {
+        let lines = [
+            r#": 1609459200:0;git commit -m "first \"#,
+            r#"second""#,
+        ];
+        let entries = parse_zsh_history(lines);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].timestamp, Some(1609459200));
+        assert_eq!(entries[0].command, "git commit -m \"first \nsecond\"");
+    }
+
+    #[test]
+    fn test_parse_bash_history_epoch_comment() {
+        let lines = ["#1609459200", "git push origin main"];
+        let entries = parse_bash_history(lines);
+        assert_eq!(
+            entries,
+            vec![HistoryEntry {
+                timestamp: Some(1609459200),
+                command: "git push origin main".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_bash_history_without_timestamp() {
+        let lines = ["git status"];
+        let entries = parse_bash_history(lines);
+        assert_eq!(
+            entries,
+            vec![HistoryEntry {
+                timestamp: None,
+                command: "git status".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_bash_history_continuation() {
+        let lines = ["#1609459200", r#"git commit -m "first \"#, r#"second""#];
+        let entries = parse_bash_history(lines);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].timestamp, Some(1609459200));
+        assert_eq!(entries[0].command, "git commit -m \"first \nsecond\"");
+    }
+
+    #[test]
+    fn test_parse_bash_history_ignores_non_command_hash_comment() {
+        // A lone comment with no command never yields a dangling entry.
+        let lines = ["#1609459200"];
+        let entries = parse_bash_history(lines);
+        assert!(entries.is_empty());
+    }
+}
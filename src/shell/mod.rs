@@ -1,13 +1,20 @@
 mod bash;
 mod detect;
 mod fish;
+mod generator;
+pub mod history;
 mod output;
+mod pty;
+mod shell_config;
 mod zsh;
 
 pub use bash::Bash;
 pub use detect::{detect_shell, Shell, ShellType};
 pub use fish::Fish;
+pub use generator::ShellGenerator;
+pub use history::HistoryEntry;
 pub use output::{get_output, get_raw_command_from_history};
+pub use shell_config::ShellConfig;
 pub use zsh::Zsh;
 
 use crate::config::Settings;
@@ -33,10 +40,14 @@ pub trait ShellOperations {
     /// Gets the last N commands from shell history.
     fn get_history(&self, limit: usize) -> Result<Vec<String>>;
 
-    /// Expands aliases in the given command.
+    /// Returns this shell's env/alias state, built once when the shell was
+    /// constructed.
+    fn shell_config(&self) -> &ShellConfig;
+
+    /// Expands aliases in the given command, recursing through chained
+    /// aliases via [`ShellConfig::expand_aliases`].
     fn expand_aliases(&self, command: &str) -> String {
-        // Default implementation: no expansion
-        command.to_string()
+        self.shell_config().expand_aliases(command)
     }
 
     /// Adds a command to shell history.
@@ -1,14 +1,25 @@
+use super::history::{parse_bash_history, parse_zsh_history};
+use super::pty;
 use crate::config::Settings;
 use crate::error::{Result, TheFuckError};
+use crate::executor::create_command;
+use crate::types::CommandOutput;
 use std::collections::HashMap;
-use std::process::{Command, Stdio};
+use std::io::Read;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 /// Gets the output of a command by re-running it.
 ///
 /// This is the default method - we re-execute the command and capture its output.
 /// The command is run with a timeout based on whether it's a slow command.
-pub fn get_output(script: &str, expanded: &str, settings: &Settings) -> Result<Option<String>> {
+pub fn get_output(
+    script: &str,
+    expanded: &str,
+    settings: &Settings,
+) -> Result<Option<CommandOutput>> {
     let timeout = settings.get_timeout(script);
 
     tracing::debug!(
@@ -21,12 +32,24 @@ pub fn get_output(script: &str, expanded: &str, settings: &Settings) -> Result<O
     let mut env: HashMap<String, String> = std::env::vars().collect();
     env.extend(settings.env.clone());
 
-    // Run the command
-    let output = run_with_timeout(expanded, &env, Duration::from_secs(timeout))?;
+    // Run the command, preferring a PTY when configured so tools that
+    // branch on `isatty()` (colorized git/ls/grep/apt, ...) behave the
+    // same as they did for the user.
+    let output = if settings.pty_output {
+        match pty::run_with_timeout(expanded, &env, Duration::from_secs(timeout), settings) {
+            Ok(output) => output,
+            Err(e) => {
+                tracing::debug!("PTY execution failed, falling back to piped: {}", e);
+                run_with_timeout(expanded, &env, Duration::from_secs(timeout), settings)?
+            }
+        }
+    } else {
+        run_with_timeout(expanded, &env, Duration::from_secs(timeout), settings)?
+    };
 
     match output {
         Some(out) => {
-            tracing::debug!("Received output ({} bytes)", out.len());
+            tracing::debug!("Received output ({} bytes)", out.combined().len());
             Ok(Some(out))
         }
         None => {
@@ -37,70 +60,185 @@ pub fn get_output(script: &str, expanded: &str, settings: &Settings) -> Result<O
 }
 
 /// Runs a command with a timeout.
+///
+/// Stdout and stderr are drained by dedicated reader threads started right
+/// after spawn, independently of whether the child has exited yet. Piped
+/// output only has an OS-buffered pipe (~64KB) behind it; a command that
+/// writes more than that before exiting would otherwise block on write
+/// while nothing reads the pipe until `wait_with_output` is called after
+/// exit is observed - starving it until the timeout fires and its output
+/// gets killed along with it. Draining concurrently also means a timeout
+/// doesn't have to discard output: whatever the readers captured before
+/// the kill is still returned.
 fn run_with_timeout(
     command: &str,
     env: &HashMap<String, String>,
     timeout: Duration,
-) -> Result<Option<String>> {
+    settings: &Settings,
+) -> Result<Option<CommandOutput>> {
     // Determine the shell to use
     let shell = if cfg!(windows) { "cmd" } else { "sh" };
     let shell_arg = if cfg!(windows) { "/C" } else { "-c" };
 
-    let mut child = Command::new(shell)
-        .arg(shell_arg)
+    let mut cmd = create_command(shell);
+    cmd.arg(shell_arg)
         .arg(command)
         .envs(env)
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
+        .stderr(Stdio::piped());
+    apply_resource_limits(&mut cmd, settings);
+
+    let mut child = cmd
         .spawn()
         .map_err(|e| TheFuckError::ExecutionError(e.to_string()))?;
 
-    // Wait for the command with timeout
-    match wait_with_timeout(&mut child, timeout) {
-        Ok(true) => {
-            // Command completed - get output
-            let output = child
-                .wait_with_output()
-                .map_err(|e| TheFuckError::ExecutionError(e.to_string()))?;
-
-            // Combine stdout and stderr
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let stderr = String::from_utf8_lossy(&output.stderr);
-
-            let combined = if !stderr.is_empty() {
-                format!("{}{}", stdout, stderr)
-            } else {
-                stdout.to_string()
-            };
-
-            Ok(Some(combined))
-        }
-        Ok(false) => {
-            // Timeout - kill the process
+    let stdout = child.stdout.take().expect("stdout was piped");
+    let stderr = child.stderr.take().expect("stderr was piped");
+    let stdout_reader = spawn_reader(stdout);
+    let stderr_reader = spawn_reader(stderr);
+
+    let status = wait_for_exit(&mut child, timeout)?;
+    let exit_code = match status {
+        Some(status) => status.code(),
+        None => {
             let _ = child.kill();
-            Ok(None)
+            let _ = child.wait();
+            None
+        }
+    };
+
+    // The child exiting on its own closes its end of the pipes, so the
+    // readers hit EOF promptly - wait for that as normal. A child killed
+    // after the timeout is a different story: a grandchild that inherited
+    // the pipe's write end can keep it open indefinitely even though the
+    // child itself is dead, so `read_to_end` would never see EOF. Bound
+    // that wait instead of joining unconditionally, so the timeout path
+    // still returns - with whatever partial output the readers drained
+    // before the deadline, same as the kill itself discarding nothing.
+    let grace = if exit_code.is_some() {
+        None
+    } else {
+        Some(Duration::from_millis(200))
+    };
+    let stdout = stdout_reader.finish(grace);
+    let stderr = stderr_reader.finish(grace);
+
+    Ok(Some(CommandOutput {
+        stdout,
+        stderr,
+        exit_code,
+    }))
+}
+
+/// Applies `settings`'s resource caps to `cmd` via a `pre_exec` hook, so a
+/// runaway or fork-bombing re-run can't wedge the user's machine beyond
+/// what the wall-clock timeout alone would catch. Only the caps that are
+/// set are applied. Unix only; a no-op when no cap is set, and on non-Unix
+/// targets.
+#[cfg(unix)]
+pub(crate) fn apply_resource_limits(cmd: &mut Command, settings: &Settings) {
+    use std::os::unix::process::CommandExt;
+
+    let max_cpu_seconds = settings.max_cpu_seconds;
+    let max_memory_bytes = settings.max_memory_bytes;
+    let max_output_bytes = settings.max_output_bytes;
+
+    if max_cpu_seconds.is_none() && max_memory_bytes.is_none() && max_output_bytes.is_none() {
+        return;
+    }
+
+    // Safety: the closure only calls async-signal-safe `setrlimit`.
+    unsafe {
+        cmd.pre_exec(move || {
+            if let Some(seconds) = max_cpu_seconds {
+                rlimit::setrlimit(rlimit::Resource::CPU, seconds, seconds)?;
+            }
+            if let Some(bytes) = max_memory_bytes {
+                rlimit::setrlimit(rlimit::Resource::AS, bytes, bytes)?;
+            }
+            if let Some(bytes) = max_output_bytes {
+                rlimit::setrlimit(rlimit::Resource::FSIZE, bytes, bytes)?;
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn apply_resource_limits(_cmd: &mut Command, _settings: &Settings) {}
+
+/// A reader thread draining into a shared buffer, so its output is
+/// available even if the thread itself never finishes (see [`DrainHandle::finish`]).
+struct DrainHandle {
+    buffer: Arc<Mutex<Vec<u8>>>,
+    done_rx: mpsc::Receiver<()>,
+}
+
+impl DrainHandle {
+    /// Returns what the reader has drained so far, decoded lossily as
+    /// UTF-8. With `grace: None`, waits for the reader to hit EOF first
+    /// (the normal case - the pipe's write end is already closed). With
+    /// `grace: Some(d)`, waits at most `d` for EOF before snapshotting the
+    /// buffer anyway, so a reader stuck on a pipe a grandchild still holds
+    /// open doesn't block the caller forever.
+    fn finish(self, grace: Option<Duration>) -> String {
+        match grace {
+            Some(d) => {
+                let _ = self.done_rx.recv_timeout(d);
+            }
+            None => {
+                let _ = self.done_rx.recv();
+            }
         }
-        Err(e) => Err(e),
+        let buf = self.buffer.lock().unwrap_or_else(|e| e.into_inner());
+        String::from_utf8_lossy(&buf).to_string()
     }
 }
 
-/// Waits for a child process with a timeout.
-/// Returns true if the process completed, false if it timed out.
-fn wait_with_timeout(
-    child: &mut std::process::Child,
+/// Spawns a thread that reads `reader` into a shared buffer until EOF,
+/// signaling completion on a channel rather than being joined directly -
+/// see [`DrainHandle::finish`] for why.
+fn spawn_reader<R: Read + Send + 'static>(mut reader: R) -> DrainHandle {
+    let buffer = Arc::new(Mutex::new(Vec::new()));
+    let buffer_for_thread = Arc::clone(&buffer);
+    let (done_tx, done_rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let mut chunk = [0u8; 8192];
+        loop {
+            match reader.read(&mut chunk) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    if let Ok(mut buf) = buffer_for_thread.lock() {
+                        buf.extend_from_slice(&chunk[..n]);
+                    }
+                }
+            }
+        }
+        let _ = done_tx.send(());
+    });
+
+    DrainHandle { buffer, done_rx }
+}
+
+/// Waits for `child` to exit, polling at a short interval so fast commands
+/// aren't held up by a coarse poll granularity.
+/// Returns its exit status if it exited on its own, `None` if `timeout` elapsed.
+fn wait_for_exit(
+    child: &mut Child,
     timeout: Duration,
-) -> Result<bool> {
+) -> Result<Option<std::process::ExitStatus>> {
     let start = std::time::Instant::now();
-    let poll_interval = Duration::from_millis(100);
+    let poll_interval = Duration::from_millis(5);
 
     loop {
         match child.try_wait() {
-            Ok(Some(_)) => return Ok(true), // Process completed
+            Ok(Some(status)) => return Ok(Some(status)),
             Ok(None) => {
                 // Still running
                 if start.elapsed() >= timeout {
-                    return Ok(false); // Timed out
+                    return Ok(None); // Timed out
                 }
                 std::thread::sleep(poll_interval);
             }
@@ -114,15 +252,18 @@ fn wait_with_timeout(
 /// The shell alias sets TF_HISTORY with the last N commands from history.
 /// We need to extract the most recent failed command.
 pub fn get_raw_command_from_history(history: &str) -> Option<String> {
-    // TF_HISTORY contains multiple lines from fc -ln -10
-    // The last non-empty line is typically the failed command
-    // But we need to skip the 'thefuck' or alias invocation itself
-
-    let lines: Vec<&str> = history
-        .lines()
-        .map(|l| l.trim())
+    // TF_HISTORY contains multiple lines from fc -ln -10. `fc` normally
+    // strips zsh/bash history metadata already, but some configurations
+    // (e.g. HISTTIMEFORMAT leaking into `fc -l`) pass it through, so reuse
+    // the shared history parsers to drop any stray timestamp/comment lines
+    // before applying the skip-logic below.
+    let zsh_stripped = parse_zsh_history(history.lines()).into_iter().map(|e| e.command);
+    let lines: Vec<String> = parse_bash_history(zsh_stripped)
+        .into_iter()
+        .map(|entry| entry.command.trim().to_string())
         .filter(|l| !l.is_empty())
         .collect();
+    let lines: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
 
     // Find the last command that isn't the thefuck invocation
     for line in lines.iter().rev() {
@@ -162,10 +303,138 @@ mod tests {
         assert_eq!(result, Some("git psuh origin main".to_string()));
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_get_output_degrades_on_timeout_instead_of_hanging() {
+        // A command that outlives wait_command should be killed with no
+        // exit code rather than hanging the correction flow.
+        let settings = Settings {
+            wait_command: 0,
+            ..Settings::default()
+        };
+        let result = get_output("sleep 5", "sleep 5", &settings)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.exit_code, None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_get_output_with_pty_enabled() {
+        let settings = Settings {
+            pty_output: true,
+            ..Settings::default()
+        };
+        let result = get_output("echo hi", "echo hi", &settings).unwrap();
+        assert!(result.unwrap().combined().contains("hi"));
+    }
+
     #[test]
     fn test_get_raw_command_skips_fuck() {
         let history = "bad_command\nfuck";
         let result = get_raw_command_from_history(history);
         assert_eq!(result, Some("bad_command".to_string()));
     }
+
+    #[test]
+    fn test_get_raw_command_from_history_strips_zsh_extended_metadata() {
+        let history = ": 1609459200:0;git psuh origin main\n: 1609459201:0;fuck";
+        let result = get_raw_command_from_history(history);
+        assert_eq!(result, Some("git psuh origin main".to_string()));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_with_timeout_drains_output_larger_than_a_pipe_buffer() {
+        // A command writing well past the ~64KB OS pipe buffer before
+        // exiting used to deadlock the old wait-then-read approach.
+        let env = HashMap::new();
+        let result = run_with_timeout(
+            "for i in $(seq 1 20000); do echo line$i; done",
+            &env,
+            Duration::from_secs(10),
+            &Settings::default(),
+        )
+        .unwrap();
+        let output = result.unwrap();
+        assert!(output.stdout.contains("line1\n"));
+        assert!(output.stdout.contains("line20000"));
+        assert_eq!(output.exit_code, Some(0));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_with_timeout_returns_partial_output_on_timeout() {
+        let env = HashMap::new();
+        let result = run_with_timeout(
+            "echo partial; sleep 5",
+            &env,
+            Duration::from_millis(200),
+            &Settings::default(),
+        )
+        .unwrap();
+        // Previously a timeout discarded everything and returned None.
+        let output = result.unwrap();
+        assert_eq!(output.stdout, "partial\n");
+        assert_eq!(output.exit_code, None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_with_timeout_returns_promptly_despite_grandchild_holding_pipe_open() {
+        // The backgrounded `sleep` inherits the stdout/stderr pipe's write
+        // end and outlives the killed parent, so it alone would keep
+        // `read_to_end` from ever seeing EOF. The bounded drain must still
+        // return well before the grandchild's own sleep finishes.
+        let env = HashMap::new();
+        let start = std::time::Instant::now();
+        let result = run_with_timeout(
+            "(sleep 5 &); echo partial; sleep 5",
+            &env,
+            Duration::from_millis(200),
+            &Settings::default(),
+        )
+        .unwrap();
+        let output = result.unwrap();
+        assert_eq!(output.exit_code, None);
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_with_timeout_captures_exit_code_and_separate_streams() {
+        let env = HashMap::new();
+        let result = run_with_timeout(
+            "echo out; echo err 1>&2; exit 3",
+            &env,
+            Duration::from_secs(5),
+            &Settings::default(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(result.stdout, "out\n");
+        assert_eq!(result.stderr, "err\n");
+        assert_eq!(result.exit_code, Some(3));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_with_timeout_applies_cpu_limit() {
+        let env = HashMap::new();
+        let settings = Settings {
+            max_cpu_seconds: Some(1),
+            ..Settings::default()
+        };
+        // A busy-loop that would otherwise run past the wall-clock timeout
+        // should instead be killed by SIGXCPU once it exceeds 1 CPU second.
+        let result = run_with_timeout(
+            "while :; do :; done",
+            &env,
+            Duration::from_secs(10),
+            &settings,
+        )
+        .unwrap();
+        let output = result.unwrap();
+        assert_ne!(output.exit_code, Some(0));
+    }
 }
@@ -0,0 +1,198 @@
+//! PTY-backed command execution.
+//!
+//! [`crate::shell::output::get_output`] normally re-runs the failed command
+//! through a plain pipe, which presents the child with a non-tty stdout and
+//! silently changes behavior in tools that branch on `isatty()` (colorized
+//! `git`, `ls`, `grep`, `apt`, ...). This module allocates a real
+//! pseudo-terminal for the child instead, so the re-run sees the same kind
+//! of terminal the user's original command did.
+//!
+//! Unix only - [`run_with_timeout`] always fails fast on other platforms so
+//! callers fall back to the piped path.
+
+use crate::config::Settings;
+use crate::error::{Result, TheFuckError};
+use crate::types::CommandOutput;
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[cfg(unix)]
+pub fn run_with_timeout(
+    command: &str,
+    env: &HashMap<String, String>,
+    timeout: Duration,
+    settings: &Settings,
+) -> Result<Option<CommandOutput>> {
+    unix::run_with_timeout(command, env, timeout, settings)
+}
+
+#[cfg(not(unix))]
+pub fn run_with_timeout(
+    _command: &str,
+    _env: &HashMap<String, String>,
+    _timeout: Duration,
+    _settings: &Settings,
+) -> Result<Option<CommandOutput>> {
+    Err(TheFuckError::ExecutionError(
+        "PTY execution is only supported on Unix".to_string(),
+    ))
+}
+
+#[cfg(unix)]
+mod unix {
+    use super::*;
+    use crate::executor::create_command;
+    use nix::pty::openpty;
+    use std::io::Read;
+    extern crate libc;
+    use std::os::fd::{AsRawFd, OwnedFd, RawFd};
+    use std::os::unix::io::FromRawFd;
+    use std::process::Stdio;
+    use std::sync::mpsc;
+
+    /// Fallback window size when the controlling terminal's size can't be
+    /// determined (e.g. stdout is already redirected).
+    const FALLBACK_COLS: u16 = 80;
+    const FALLBACK_ROWS: u16 = 24;
+
+    pub fn run_with_timeout(
+        command: &str,
+        env: &HashMap<String, String>,
+        timeout: Duration,
+        settings: &Settings,
+    ) -> Result<Option<CommandOutput>> {
+        let pty = openpty(Some(&window_size()), None)
+            .map_err(|e| TheFuckError::ExecutionError(format!("openpty failed: {e}")))?;
+        let master: OwnedFd = pty.master;
+        let slave: OwnedFd = pty.slave;
+
+        // SAFETY: `libc::dup` returns a fresh, valid, owned fd duplicating
+        // the slave side, which `Stdio::from_raw_fd` then takes ownership of.
+        let slave_stdio = |fd: RawFd| -> Stdio { unsafe { Stdio::from_raw_fd(libc::dup(fd)) } };
+
+        let mut cmd = create_command("sh");
+        cmd.arg("-c")
+            .arg(command)
+            .envs(env)
+            .env("TERM", env.get("TERM").cloned().unwrap_or_else(|| "xterm-256color".to_string()))
+            .stdin(slave_stdio(slave.as_raw_fd()))
+            .stdout(slave_stdio(slave.as_raw_fd()))
+            .stderr(slave_stdio(slave.as_raw_fd()));
+        super::output::apply_resource_limits(&mut cmd, settings);
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| TheFuckError::ExecutionError(e.to_string()))?;
+
+        // Drop our copy of the slave so the master sees EOF once the
+        // child's own copies close on exit.
+        drop(slave);
+
+        // Read everything from the master on a background thread, since a
+        // blocking read can't be interleaved with polling the child's exit
+        // status on the same thread.
+        let mut master_file = std::fs::File::from(master);
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            let _ = master_file.read_to_end(&mut buf);
+            let _ = tx.send(buf);
+        });
+
+        let start = std::time::Instant::now();
+        let exit_code = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status.code(),
+                Ok(None) => {
+                    if start.elapsed() >= timeout {
+                        let _ = child.kill();
+                        return Ok(None);
+                    }
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+                Err(e) => return Err(TheFuckError::ExecutionError(e.to_string())),
+            }
+        };
+
+        let remaining = timeout.saturating_sub(start.elapsed());
+        let output = rx
+            .recv_timeout(remaining.max(Duration::from_millis(50)))
+            .unwrap_or_default();
+
+        // A PTY presents the child with a single terminal stream, so stdout
+        // and stderr can't be told apart here - everything lands in stdout.
+        Ok(Some(CommandOutput {
+            stdout: String::from_utf8_lossy(&output).to_string(),
+            stderr: String::new(),
+            exit_code,
+        }))
+    }
+
+    /// Returns the controlling terminal's window size via `TIOCGWINSZ`,
+    /// falling back to `FALLBACK_COLS`x`FALLBACK_ROWS` when stdout isn't a
+    /// terminal (e.g. output already redirected).
+    fn window_size() -> nix::pty::Winsize {
+        let mut ws: libc::winsize = unsafe { std::mem::zeroed() };
+        let ok = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut ws) } == 0;
+
+        if ok && ws.ws_row != 0 && ws.ws_col != 0 {
+            nix::pty::Winsize {
+                ws_row: ws.ws_row,
+                ws_col: ws.ws_col,
+                ws_xpixel: ws.ws_xpixel,
+                ws_ypixel: ws.ws_ypixel,
+            }
+        } else {
+            nix::pty::Winsize {
+                ws_row: FALLBACK_ROWS,
+                ws_col: FALLBACK_COLS,
+                ws_xpixel: 0,
+                ws_ypixel: 0,
+            }
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pty_run_captures_output() {
+        let env = HashMap::new();
+        let result = run_with_timeout(
+            "echo hello",
+            &env,
+            Duration::from_secs(5),
+            &Settings::default(),
+        )
+        .unwrap();
+        assert!(result.unwrap().stdout.contains("hello"));
+    }
+
+    #[test]
+    fn test_pty_run_times_out() {
+        let env = HashMap::new();
+        let result = run_with_timeout(
+            "sleep 5",
+            &env,
+            Duration::from_millis(100),
+            &Settings::default(),
+        )
+        .unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_pty_run_applies_cpu_limit() {
+        let env = HashMap::new();
+        let settings = Settings {
+            max_cpu_seconds: Some(1),
+            ..Settings::default()
+        };
+        let result = run_with_timeout("while :; do :; done", &env, Duration::from_secs(10), &settings)
+            .unwrap();
+        let output = result.unwrap();
+        assert_ne!(output.exit_code, Some(0));
+    }
+}
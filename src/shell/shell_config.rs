@@ -0,0 +1,112 @@
+//! Shared per-shell alias/environment state.
+//!
+//! Each `ShellOperations` implementation used to snapshot the process
+//! environment and re-parse `TF_SHELL_ALIASES` independently, and alias
+//! expansion only ever substituted one token deep. `ShellConfig` centralizes
+//! that state - built once per invocation from whatever alias lines the
+//! shell-specific constructor parsed - and gives every implementation the
+//! same recursive expansion behavior.
+
+use super::is_builtin;
+use std::collections::{HashMap, HashSet};
+
+/// Environment and alias state for the current shell invocation.
+#[derive(Debug, Clone, Default)]
+pub struct ShellConfig {
+    /// Environment variables the shell had set, layered on top of the
+    /// process environment when re-running a command.
+    pub env: HashMap<String, String>,
+    /// Alias name -> expansion, as parsed from the shell's own alias
+    /// listing (e.g. `TF_SHELL_ALIASES`).
+    pub aliases: HashMap<String, String>,
+}
+
+impl ShellConfig {
+    /// Builds a config from a process environment snapshot and a parsed
+    /// alias map.
+    pub fn new(env: HashMap<String, String>, aliases: HashMap<String, String>) -> Self {
+        Self { env, aliases }
+    }
+
+    /// Expands `command`'s leading word through `aliases`, following
+    /// chained aliases (an alias that expands to another alias) until the
+    /// leading word is a shell builtin, has no further alias, or would
+    /// revisit an alias already expanded this call - guarding against
+    /// cycles like `alias ls=ls` or `a=b`/`b=a`.
+    pub fn expand_aliases(&self, command: &str) -> String {
+        let mut current = command.to_string();
+        let mut expanded_already = HashSet::new();
+
+        loop {
+            let mut parts = current.splitn(2, ' ');
+            let first_word = parts.next().unwrap_or("");
+            let rest = parts.next();
+
+            if first_word.is_empty()
+                || is_builtin(first_word)
+                || !expanded_already.insert(first_word.to_string())
+            {
+                return current;
+            }
+
+            let Some(expansion) = self.aliases.get(first_word) else {
+                return current;
+            };
+
+            current = match rest {
+                Some(rest) => format!("{expansion} {rest}"),
+                None => expansion.clone(),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(aliases: &[(&str, &str)]) -> ShellConfig {
+        ShellConfig::new(
+            HashMap::new(),
+            aliases
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_expand_aliases_single_hop() {
+        let config = config(&[("ll", "ls -la")]);
+        assert_eq!(config.expand_aliases("ll /tmp"), "ls -la /tmp");
+    }
+
+    #[test]
+    fn test_expand_aliases_chained() {
+        let config = config(&[("g", "git"), ("git", "git --no-pager")]);
+        assert_eq!(
+            config.expand_aliases("g status"),
+            "git --no-pager status"
+        );
+    }
+
+    #[test]
+    fn test_expand_aliases_stops_at_builtin() {
+        let config = config(&[("cd", "cd -v")]);
+        assert_eq!(config.expand_aliases("cd /tmp"), "cd /tmp");
+    }
+
+    #[test]
+    fn test_expand_aliases_no_match_is_noop() {
+        let config = config(&[("ll", "ls -la")]);
+        assert_eq!(config.expand_aliases("git status"), "git status");
+    }
+
+    #[test]
+    fn test_expand_aliases_breaks_cycles() {
+        let config = config(&[("a", "b"), ("b", "a")]);
+        // Must terminate rather than looping forever.
+        let result = config.expand_aliases("a");
+        assert!(result == "a" || result == "b");
+    }
+}
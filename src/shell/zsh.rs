@@ -1,4 +1,5 @@
-use super::{ShellOperations, ShellType};
+use super::history::parse_zsh_history;
+use super::{ShellConfig, ShellOperations, ShellType};
 use crate::config::Settings;
 use crate::error::Result;
 use std::collections::HashMap;
@@ -10,11 +11,13 @@ use std::path::PathBuf;
 /// Zsh shell implementation.
 pub struct Zsh {
     settings: Settings,
+    config: ShellConfig,
 }
 
 impl Zsh {
     pub fn new(settings: Settings) -> Self {
-        Self { settings }
+        let config = ShellConfig::new(env::vars().collect(), Self::get_aliases_from_env());
+        Self { settings, config }
     }
 
     /// Parses an alias line from zsh alias output.
@@ -48,16 +51,6 @@ impl Zsh {
                 .unwrap_or_else(|| PathBuf::from("~/.zsh_history"))
         })
     }
-
-    /// Extracts command from zsh history line.
-    /// Zsh history format: `: timestamp:0;command`
-    fn script_from_history(line: &str) -> Option<String> {
-        if line.contains(';') {
-            Some(line.split_once(';')?.1.to_string())
-        } else {
-            None
-        }
-    }
 }
 
 impl ShellOperations for Zsh {
@@ -107,32 +100,20 @@ impl ShellOperations for Zsh {
 
         let file = fs::File::open(&history_file)?;
         let reader = BufReader::new(file);
-        let lines: Vec<String> = reader
-            .lines()
-            .map_while(|l| l.ok())
-            .filter_map(|l| Self::script_from_history(&l))
+        let lines: Vec<String> = reader.lines().map_while(|l| l.ok()).collect();
+        let commands: Vec<String> = parse_zsh_history(lines)
+            .into_iter()
+            .map(|entry| entry.command)
             .filter(|l| !l.is_empty())
             .collect();
 
-        // Return last `limit` lines
-        let start = lines.len().saturating_sub(limit);
-        Ok(lines[start..].to_vec())
+        // Return last `limit` commands
+        let start = commands.len().saturating_sub(limit);
+        Ok(commands[start..].to_vec())
     }
 
-    fn expand_aliases(&self, command: &str) -> String {
-        let aliases = Self::get_aliases_from_env();
-        let parts: Vec<&str> = command.splitn(2, ' ').collect();
-        let binary = parts[0];
-
-        if let Some(expanded) = aliases.get(binary) {
-            if parts.len() > 1 {
-                format!("{} {}", expanded, parts[1])
-            } else {
-                expanded.clone()
-            }
-        } else {
-            command.to_string()
-        }
+    fn shell_config(&self) -> &ShellConfig {
+        &self.config
     }
 
     fn put_to_history(&self, command: &str) -> Result<()> {
@@ -146,20 +127,6 @@ impl ShellOperations for Zsh {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_script_from_history() {
-        let line = ": 1609459200:0;git push origin main";
-        let result = Zsh::script_from_history(line);
-        assert_eq!(result, Some("git push origin main".to_string()));
-    }
-
-    #[test]
-    fn test_script_from_history_no_semicolon() {
-        let line = "simple command";
-        let result = Zsh::script_from_history(line);
-        assert_eq!(result, None);
-    }
-
     #[test]
     fn test_parse_alias() {
         let result = Zsh::parse_alias("ll='ls -la'");
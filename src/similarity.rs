@@ -0,0 +1,357 @@
+//! Shared "did you mean" matching.
+//!
+//! [`closest`] is the fixed Levenshtein-distance matcher used by rules that
+//! suggest a likely intended command from a small, fixed list of known
+//! candidates, such as [`crate::rules::git_not_command`] and
+//! [`crate::rules::cargo_no_command`]. [`closest_ranked`] is a pluggable
+//! variant, configurable via [`crate::config::Settings::similarity_metric`]
+//! and [`crate::config::Settings::similarity_threshold`], used by
+//! [`crate::rules::no_command`] where the candidate list is every
+//! executable on `PATH` and suggestion quality benefits from being tunable.
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of suggestions returned by [`closest`].
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Similarity backend selectable via `Settings::similarity_metric`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimilarityMetric {
+    /// Prefix-weighted similarity; tolerant of typos near the end of a word.
+    JaroWinkler,
+    /// Classic insert/delete/substitute edit distance.
+    Levenshtein,
+    /// Levenshtein plus adjacent-transposition as a single edit, so
+    /// `gti` -> `git` and `psuh` -> `push` cost 1 instead of 2.
+    DamerauLevenshtein,
+}
+
+impl Default for SimilarityMetric {
+    fn default() -> Self {
+        SimilarityMetric::DamerauLevenshtein
+    }
+}
+
+/// Computes the Levenshtein edit distance between two strings.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let n = b.len();
+
+    let mut dp: Vec<usize> = (0..=n).collect();
+
+    for (i, &ca) in a.iter().enumerate() {
+        let mut prev = dp[0];
+        dp[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let tmp = dp[j + 1];
+            dp[j + 1] = if ca == cb {
+                prev
+            } else {
+                1 + dp[j + 1].min(dp[j]).min(prev)
+            };
+            prev = tmp;
+        }
+    }
+
+    dp[n]
+}
+
+/// Computes the Damerau-Levenshtein edit distance between two strings,
+/// where swapping two adjacent characters counts as a single edit instead
+/// of the two substitutions plain Levenshtein would charge for it.
+pub fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (n, m) = (a.len(), b.len());
+
+    let mut dp = vec![vec![0usize; m + 1]; n + 1];
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for j in 0..=m {
+        dp[0][j] = j;
+    }
+
+    for i in 1..=n {
+        for j in 1..=m {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            dp[i][j] = (dp[i - 1][j] + 1)
+                .min(dp[i][j - 1] + 1)
+                .min(dp[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                dp[i][j] = dp[i][j].min(dp[i - 2][j - 2] + cost);
+            }
+        }
+    }
+
+    dp[n][m]
+}
+
+/// Computes Jaro-Winkler similarity between two strings, in `[0.0, 1.0]`
+/// where `1.0` is an exact match.
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let jaro = jaro_similarity(a, b);
+    if jaro == 0.0 {
+        return 0.0;
+    }
+
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let prefix_len = a
+        .iter()
+        .zip(b.iter())
+        .take(4)
+        .take_while(|(ca, cb)| ca == cb)
+        .count() as f64;
+
+    const SCALING_FACTOR: f64 = 0.1;
+    jaro + prefix_len * SCALING_FACTOR * (1.0 - jaro)
+}
+
+fn jaro_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (a_len, b_len) = (a.len(), b.len());
+
+    if a_len == 0 && b_len == 0 {
+        return 1.0;
+    }
+    if a_len == 0 || b_len == 0 {
+        return 0.0;
+    }
+
+    let match_distance = (a_len.max(b_len) / 2).saturating_sub(1);
+
+    let mut a_matches = vec![false; a_len];
+    let mut b_matches = vec![false; b_len];
+    let mut matches = 0usize;
+
+    for i in 0..a_len {
+        let lo = i.saturating_sub(match_distance);
+        let hi = (i + match_distance + 1).min(b_len);
+        for j in lo..hi {
+            if b_matches[j] || a[i] != b[j] {
+                continue;
+            }
+            a_matches[i] = true;
+            b_matches[j] = true;
+            matches += 1;
+            break;
+        }
+    }
+
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let mut transpositions = 0usize;
+    let mut b_index = 0;
+    for (i, &matched) in a_matches.iter().enumerate() {
+        if !matched {
+            continue;
+        }
+        while !b_matches[b_index] {
+            b_index += 1;
+        }
+        if a[i] != b[b_index] {
+            transpositions += 1;
+        }
+        b_index += 1;
+    }
+
+    let matches = matches as f64;
+    (matches / a_len as f64 + matches / b_len as f64 + (matches - transpositions as f64 / 2.0) / matches) / 3.0
+}
+
+/// Returns `target`'s normalized distance to `candidate` under `metric`, in
+/// `[0.0, 1.0]` where `0.0` is identical. Edit-distance metrics are
+/// normalized by the longer string's length so the cutoff in
+/// [`closest_ranked`] means the same thing regardless of metric.
+fn normalized_distance(target: &str, candidate: &str, metric: SimilarityMetric) -> f64 {
+    match metric {
+        SimilarityMetric::JaroWinkler => 1.0 - jaro_winkler(target, candidate),
+        SimilarityMetric::Levenshtein => {
+            let longest = target.chars().count().max(candidate.chars().count());
+            if longest == 0 {
+                0.0
+            } else {
+                levenshtein(target, candidate) as f64 / longest as f64
+            }
+        }
+        SimilarityMetric::DamerauLevenshtein => {
+            let longest = target.chars().count().max(candidate.chars().count());
+            if longest == 0 {
+                0.0
+            } else {
+                damerau_levenshtein(target, candidate) as f64 / longest as f64
+            }
+        }
+    }
+}
+
+/// Returns candidates close enough to `target` under `metric` to be a
+/// plausible typo fix, ranked by ascending normalized distance (ties
+/// broken alphabetically) and capped at `max_suggestions`.
+///
+/// Unlike [`closest`]'s fixed cargo-style cutoff, the acceptance threshold
+/// here is the caller-supplied `threshold`, a normalized distance in
+/// `[0.0, 1.0]` - see `Settings::similarity_threshold`.
+pub fn closest_ranked(
+    target: &str,
+    candidates: &[String],
+    metric: SimilarityMetric,
+    threshold: f64,
+    max_suggestions: usize,
+) -> Vec<String> {
+    let mut scored: Vec<(f64, &String)> = candidates
+        .iter()
+        .filter(|candidate| candidate.as_str() != target)
+        .map(|candidate| (normalized_distance(target, candidate, metric), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .collect();
+
+    scored.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap().then_with(|| a.1.cmp(b.1)));
+    scored.truncate(max_suggestions);
+
+    scored.into_iter().map(|(_, candidate)| candidate.clone()).collect()
+}
+
+/// Returns candidates close enough to `target` to be a plausible typo fix,
+/// sorted by ascending edit distance and then alphabetically, capped at
+/// [`MAX_SUGGESTIONS`].
+///
+/// Follows cargo's own suggestion heuristic: a candidate is accepted only
+/// when its edit distance is no more than a third of the longer string's
+/// length.
+pub fn closest(target: &str, candidates: &[String]) -> Vec<String> {
+    let mut scored: Vec<(usize, &String)> = candidates
+        .iter()
+        .filter(|candidate| candidate.as_str() != target)
+        .map(|candidate| (levenshtein(target, candidate), candidate))
+        .filter(|(distance, candidate)| *distance <= target.len().max(candidate.len()) / 3)
+        .collect();
+
+    scored.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+    scored.truncate(MAX_SUGGESTIONS);
+
+    scored.into_iter().map(|(_, candidate)| candidate.clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein_identical() {
+        assert_eq!(levenshtein("build", "build"), 0);
+    }
+
+    #[test]
+    fn test_levenshtein_substitution() {
+        assert_eq!(levenshtein("buld", "build"), 1);
+    }
+
+    #[test]
+    fn test_levenshtein_transposition_counts_as_two_edits() {
+        // Plain Levenshtein has no single-edit transposition, unlike Damerau.
+        assert_eq!(levenshtein("gti", "git"), 2);
+    }
+
+    #[test]
+    fn test_damerau_levenshtein_transposition_counts_as_one_edit() {
+        assert_eq!(damerau_levenshtein("gti", "git"), 1);
+        assert_eq!(damerau_levenshtein("psuh", "push"), 1);
+    }
+
+    #[test]
+    fn test_damerau_levenshtein_matches_levenshtein_without_transpositions() {
+        assert_eq!(damerau_levenshtein("buld", "build"), levenshtein("buld", "build"));
+    }
+
+    #[test]
+    fn test_jaro_winkler_identical_is_one() {
+        assert_eq!(jaro_winkler("build", "build"), 1.0);
+    }
+
+    #[test]
+    fn test_jaro_winkler_known_reference_values() {
+        // Classic Winkler reference pairs, tolerant of float rounding.
+        assert!((jaro_winkler("MARTHA", "MARHTA") - 0.961).abs() < 0.001);
+        assert!((jaro_winkler("DIXON", "DICKSONX") - 0.813).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_jaro_winkler_disjoint_strings_is_zero() {
+        assert_eq!(jaro_winkler("abc", "xyz"), 0.0);
+    }
+
+    #[test]
+    fn test_closest_ranked_prefers_damerau_for_transpositions() {
+        let candidates = vec!["git".to_string(), "gti".to_string()];
+        let result = closest_ranked("gti", &candidates, SimilarityMetric::DamerauLevenshtein, 0.5, 3);
+        assert_eq!(result, vec!["git".to_string()]);
+    }
+
+    #[test]
+    fn test_closest_ranked_respects_threshold() {
+        let candidates = vec!["clippy".to_string()];
+        assert!(closest_ranked("buld", &candidates, SimilarityMetric::Levenshtein, 0.2, 3).is_empty());
+    }
+
+    #[test]
+    fn test_closest_ranked_caps_max_suggestions() {
+        let candidates = vec!["buile".to_string(), "bauld".to_string(), "biuld".to_string()];
+        let result = closest_ranked("build", &candidates, SimilarityMetric::DamerauLevenshtein, 0.9, 2);
+        assert_eq!(result.len(), 2);
+    }
+
+    #[test]
+    fn test_levenshtein_empty_strings() {
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("abc", ""), 3);
+        assert_eq!(levenshtein("", ""), 0);
+    }
+
+    #[test]
+    fn test_closest_finds_nearby_candidate() {
+        let candidates = vec!["build".to_string(), "test".to_string(), "run".to_string()];
+        assert_eq!(closest("buld", &candidates), vec!["build".to_string()]);
+    }
+
+    #[test]
+    fn test_closest_excludes_exact_match() {
+        let candidates = vec!["build".to_string()];
+        assert!(closest("build", &candidates).is_empty());
+    }
+
+    #[test]
+    fn test_closest_excludes_distant_candidates() {
+        let candidates = vec!["clippy".to_string()];
+        assert!(closest("buld", &candidates).is_empty());
+    }
+
+    #[test]
+    fn test_closest_sorted_by_distance_then_alphabetically() {
+        let candidates = vec!["feth".to_string(), "fetc".to_string(), "push".to_string()];
+        assert_eq!(
+            closest("fetch", &candidates),
+            vec!["fetc".to_string(), "feth".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_closest_caps_suggestion_count() {
+        let candidates = vec![
+            "baaaaa".to_string(),
+            "abaaaa".to_string(),
+            "aabaaa".to_string(),
+            "aaabaa".to_string(),
+            "aaaaba".to_string(),
+            "aaaaab".to_string(),
+        ];
+        assert_eq!(closest("aaaaaa", &candidates).len(), MAX_SUGGESTIONS);
+    }
+}
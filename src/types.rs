@@ -0,0 +1,341 @@
+//! Core types shared across the crate.
+//!
+//! This module defines the `Command` being corrected, the `Rule` trait
+//! implemented by every correction rule (built-in and user-defined), and
+//! the `CorrectedCommand` a rule produces.
+
+use crate::config::DEFAULT_PRIORITY;
+use crate::git_info::GitContext;
+use serde::Serialize;
+use std::cell::OnceCell;
+use std::fmt;
+
+/// Stdout, stderr, and exit status captured when a command was re-run, kept
+/// separate so rules can match on the exact stream or exit code instead of
+/// the combined text in [`Command::output`].
+///
+/// Not every re-run path can populate every field: a PTY re-run (see
+/// [`crate::shell::pty`]) presents the child with a single terminal stream,
+/// so its stdout/stderr can't be told apart and everything lands in
+/// `stdout` with `stderr` left empty.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CommandOutput {
+    /// Text captured from the command's stdout stream.
+    pub stdout: String,
+    /// Text captured from the command's stderr stream.
+    pub stderr: String,
+    /// The command's exit code, if it ran to completion. `None` if it was
+    /// killed after timing out.
+    pub exit_code: Option<i32>,
+}
+
+impl CommandOutput {
+    /// Returns the combined stdout+stderr text, matching the legacy view
+    /// still exposed as [`Command::output`].
+    pub fn combined(&self) -> String {
+        if self.stderr.is_empty() {
+            self.stdout.clone()
+        } else {
+            format!("{}{}", self.stdout, self.stderr)
+        }
+    }
+}
+
+/// A shell command that may need correction.
+///
+/// Wraps the raw script the user ran along with the output produced when
+/// it failed, if any. The script is split into whitespace-separated parts
+/// lazily and cached, since most rules only need the parts some of the
+/// time.
+#[derive(Debug, Clone, Serialize)]
+pub struct Command {
+    /// The raw command script as typed by the user.
+    pub script: String,
+    /// Output (stdout+stderr) captured when the command was re-run, if any.
+    pub output: Option<String>,
+    /// Structured stdout/stderr/exit_code for the same re-run, if the path
+    /// that produced `output` was able to capture it separately.
+    pub structured_output: Option<CommandOutput>,
+    #[serde(skip)]
+    script_parts: Option<Vec<String>>,
+    /// Lazily discovered and cached git repository context, keyed off the
+    /// process's current directory. `None` once initialized means the cwd
+    /// isn't inside a repository.
+    #[serde(skip)]
+    git_context: OnceCell<Option<GitContext>>,
+}
+
+impl Command {
+    /// Creates a new `Command` from a script and optional output.
+    pub fn new(script: impl Into<String>, output: Option<String>) -> Self {
+        Self {
+            script: script.into(),
+            output,
+            structured_output: None,
+            script_parts: None,
+            git_context: OnceCell::new(),
+        }
+    }
+
+    /// Attaches structured stdout/stderr/exit_code to this command,
+    /// returning it for chaining off of [`Command::new`].
+    pub fn with_structured_output(mut self, structured: CommandOutput) -> Self {
+        self.structured_output = Some(structured);
+        self
+    }
+
+    /// Splits the script into whitespace-separated parts, caching the result.
+    pub fn script_parts(&mut self) -> &Vec<String> {
+        if self.script_parts.is_none() {
+            self.script_parts = Some(
+                self.script
+                    .split_whitespace()
+                    .map(String::from)
+                    .collect(),
+            );
+        }
+        self.script_parts.as_ref().unwrap()
+    }
+
+    /// Returns this command's git repository context, discovering and
+    /// caching it on first call. `None` if the cwd isn't inside a git
+    /// repository. See [`GitContext`].
+    pub fn git_context(&self) -> Option<&GitContext> {
+        self.git_context
+            .get_or_init(GitContext::discover)
+            .as_ref()
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.script)
+    }
+}
+
+/// A corrected command suggested by a rule, ready to be run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CorrectedCommand {
+    /// The corrected command to run.
+    pub script: String,
+    /// Name of the rule that produced this correction.
+    pub rule_name: String,
+    /// Priority used to order corrections; lower runs first.
+    pub priority: i32,
+}
+
+impl CorrectedCommand {
+    /// Creates a new `CorrectedCommand`.
+    pub fn new(script: impl Into<String>, rule_name: impl Into<String>, priority: i32) -> Self {
+        Self {
+            script: script.into(),
+            rule_name: rule_name.into(),
+            priority,
+        }
+    }
+}
+
+impl PartialOrd for CorrectedCommand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CorrectedCommand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.priority.cmp(&other.priority)
+    }
+}
+
+/// Metadata describing a rule, independent of any specific command.
+///
+/// Useful for listing/introspecting rules without needing a `Command` to
+/// test them against.
+#[derive(Debug, Clone)]
+pub struct RuleInfo {
+    pub name: String,
+    pub priority: i32,
+    pub enabled_by_default: bool,
+}
+
+/// Trait implemented by every correction rule, built-in and user-defined.
+pub trait Rule {
+    /// A short, stable, snake_case identifier for the rule.
+    fn name(&self) -> &str;
+
+    /// Returns true if this rule applies to the given command.
+    fn matches(&self, command: &Command) -> bool;
+
+    /// Returns one or more corrected commands for a matched command.
+    fn get_new_command(&self, command: &Command) -> Vec<String>;
+
+    /// Priority used to order this rule's suggestions; lower runs first.
+    fn priority(&self) -> i32 {
+        DEFAULT_PRIORITY
+    }
+
+    /// Whether this rule is enabled unless explicitly excluded.
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    /// Whether this rule needs re-run command output to be considered.
+    fn requires_output(&self) -> bool {
+        true
+    }
+
+    /// Returns a short, human-readable explanation of what this rule's
+    /// correction does for the given command, for display in the
+    /// interactive selector's preview pane. Rules that don't implement
+    /// this fall back to a script diff.
+    fn explain(&self, _command: &Command) -> Option<String> {
+        None
+    }
+
+    /// Literal substrings in the command's output that must be present for
+    /// this rule to possibly match. An empty slice (the default) means the
+    /// rule is always a candidate and `matches()` is consulted directly.
+    ///
+    /// Used by [`crate::corrector::Corrector`] to prefilter rules with an
+    /// Aho-Corasick scan instead of calling every rule's `matches()`.
+    /// Declaring triggers is an optimization only - `matches()` must still
+    /// independently confirm the match.
+    fn output_triggers(&self) -> Vec<&str> {
+        vec![]
+    }
+
+    /// Like [`Rule::output_triggers`], but matched against the command's
+    /// script instead of its output.
+    fn script_triggers(&self) -> Vec<&str> {
+        vec![]
+    }
+
+    /// Whether `corrected` is risky enough that the user should have to
+    /// explicitly re-confirm it instead of accepting it with the usual
+    /// single keypress. Defaults to the shared wildcard+privilege heuristic
+    /// in [`crate::danger`]; rules that produce their own inherently
+    /// dangerous corrections may override this with something more precise.
+    fn is_dangerous(&self, corrected: &str) -> bool {
+        crate::danger::is_dangerous_script(corrected)
+    }
+
+    /// Returns whether `command`'s captured stderr contains `needle`.
+    /// Returns `false` if no structured output was captured for this
+    /// command (e.g. it wasn't re-run, or was re-run through a path that
+    /// only produced combined text).
+    fn stderr_contains(&self, command: &Command, needle: &str) -> bool {
+        command
+            .structured_output
+            .as_ref()
+            .is_some_and(|out| out.stderr.contains(needle))
+    }
+
+    /// Returns `command`'s captured exit code, if structured output is
+    /// available and the re-run completed rather than timing out.
+    fn exit_code(&self, command: &Command) -> Option<i32> {
+        command.structured_output.as_ref().and_then(|out| out.exit_code)
+    }
+
+    /// Returns metadata about this rule.
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            name: self.name().to_string(),
+            priority: self.priority(),
+            enabled_by_default: self.enabled_by_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_script_parts() {
+        let mut cmd = Command::new("git push origin main", None);
+        assert_eq!(cmd.script_parts(), &vec!["git", "push", "origin", "main"]);
+    }
+
+    #[test]
+    fn test_command_display() {
+        let cmd = Command::new("git push", None);
+        assert_eq!(format!("{}", cmd), "git push");
+    }
+
+    #[test]
+    fn test_command_output_combined() {
+        let out = CommandOutput {
+            stdout: "ok\n".to_string(),
+            stderr: "warn\n".to_string(),
+            exit_code: Some(1),
+        };
+        assert_eq!(out.combined(), "ok\nwarn\n");
+    }
+
+    #[test]
+    fn test_rule_stderr_contains_and_exit_code_without_structured_output() {
+        struct Dummy;
+        impl Rule for Dummy {
+            fn name(&self) -> &str {
+                "dummy"
+            }
+            fn matches(&self, _command: &Command) -> bool {
+                false
+            }
+            fn get_new_command(&self, _command: &Command) -> Vec<String> {
+                vec![]
+            }
+        }
+
+        let cmd = Command::new("git push", Some("rejected".to_string()));
+        let rule = Dummy;
+        assert!(!rule.stderr_contains(&cmd, "rejected"));
+        assert_eq!(rule.exit_code(&cmd), None);
+    }
+
+    #[test]
+    fn test_rule_stderr_contains_and_exit_code_with_structured_output() {
+        struct Dummy;
+        impl Rule for Dummy {
+            fn name(&self) -> &str {
+                "dummy"
+            }
+            fn matches(&self, _command: &Command) -> bool {
+                false
+            }
+            fn get_new_command(&self, _command: &Command) -> Vec<String> {
+                vec![]
+            }
+        }
+
+        let cmd = Command::new("git push", Some("rejected".to_string())).with_structured_output(
+            CommandOutput {
+                stdout: String::new(),
+                stderr: "rejected".to_string(),
+                exit_code: Some(1),
+            },
+        );
+        let rule = Dummy;
+        assert!(rule.stderr_contains(&cmd, "rejected"));
+        assert_eq!(rule.exit_code(&cmd), Some(1));
+    }
+
+    #[test]
+    fn test_command_git_context_caches() {
+        let cmd = Command::new("git status", None);
+        // Whatever the test runner's cwd resolves to, both calls must agree
+        // - the second is served from the cache rather than re-discovering.
+        assert_eq!(cmd.git_context(), cmd.git_context());
+    }
+
+    #[test]
+    fn test_corrected_command_ordering() {
+        let mut commands = vec![
+            CorrectedCommand::new("b", "rule_b", 200),
+            CorrectedCommand::new("a", "rule_a", 100),
+        ];
+        commands.sort();
+        assert_eq!(commands[0].script, "a");
+        assert_eq!(commands[1].script, "b");
+    }
+}
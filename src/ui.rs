@@ -1,5 +1,7 @@
 use crate::config::Settings;
-use crate::types::CorrectedCommand;
+use crate::executor::create_command;
+use crate::learning::Learning;
+use crate::types::{CorrectedCommand, Rule};
 use colored::{control::set_override, Colorize};
 use crossterm::{
     cursor,
@@ -8,6 +10,12 @@ use crossterm::{
     terminal::{self, ClearType},
 };
 use std::io::{self, Write};
+use std::process::Stdio;
+
+/// Command being fixed, as shown by the preview pane. Aliased so this
+/// module's uses of `Command` (the `std::process::Command` spawned to run an
+/// external chooser, via [`create_command`]) aren't shadowed.
+type OriginalCommand = crate::types::Command;
 
 /// Actions that can be performed in the command selector.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -25,6 +33,9 @@ pub enum Action {
 /// Command selector for interactive selection.
 pub struct CommandSelector {
     commands: Vec<CorrectedCommand>,
+    /// Preview text for each candidate, parallel to `commands`. Empty when
+    /// no preview was requested (e.g. via `new`).
+    previews: Vec<String>,
     index: usize,
 }
 
@@ -34,10 +45,38 @@ impl CommandSelector {
         if commands.is_empty() {
             None
         } else {
-            Some(Self { commands, index: 0 })
+            let previews = vec![String::new(); commands.len()];
+            Some(Self { commands, previews, index: 0 })
         }
     }
 
+    /// Creates a new command selector with a preview computed for each
+    /// candidate: the matching rule's [`Rule::explain`], or a diff between
+    /// the original command and the candidate's script if the rule doesn't
+    /// implement one.
+    pub fn with_previews(
+        commands: Vec<CorrectedCommand>,
+        original: &OriginalCommand,
+        rules: &[&dyn Rule],
+    ) -> Option<Self> {
+        if commands.is_empty() {
+            return None;
+        }
+
+        let previews = commands
+            .iter()
+            .map(|c| build_preview(c, original, rules))
+            .collect();
+
+        Some(Self { commands, previews, index: 0 })
+    }
+
+    /// Returns the preview text for the currently selected command, or an
+    /// empty string if none was computed.
+    pub fn current_preview(&self) -> &str {
+        &self.previews[self.index]
+    }
+
     /// Moves to the next command (wrapping around).
     pub fn next(&mut self) {
         self.index = (self.index + 1) % self.commands.len();
@@ -121,15 +160,45 @@ fn key_to_action(key: KeyEvent) -> Option<Action> {
     }
 }
 
-/// Displays the confirmation prompt for a command.
-fn show_confirmation(selector: &CommandSelector, no_colors: bool) {
+/// Builds the preview text for a candidate correction: the matching rule's
+/// explanation if it has one, otherwise a diff between the original command
+/// and the candidate's script.
+fn build_preview(
+    corrected: &CorrectedCommand,
+    original: &OriginalCommand,
+    rules: &[&dyn Rule],
+) -> String {
+    let explanation = rules
+        .iter()
+        .find(|rule| rule.name() == corrected.rule_name)
+        .and_then(|rule| rule.explain(original));
+
+    explanation.unwrap_or_else(|| diff_scripts(&original.script, &corrected.script))
+}
+
+/// Renders a minimal line-oriented diff between the original and corrected
+/// script, in the style of a unified diff without the hunk headers.
+fn diff_scripts(original: &str, corrected: &str) -> String {
+    format!("- {}\n+ {}", original, corrected)
+}
+
+/// Displays the confirmation prompt for a command, followed by its preview
+/// pane, returning the number of preview lines rendered so the caller can
+/// clear them again before the next redraw.
+fn show_confirmation(selector: &CommandSelector, no_colors: bool) -> u16 {
     let cmd = selector.current();
     let index = selector.current_index();
     let total = selector.len();
 
-    // Clear the current line
+    // Clear everything from the prompt line down, since the preview pane
+    // below it may vary in height between candidates.
     let mut stderr = io::stderr();
-    execute!(stderr, cursor::MoveToColumn(0), terminal::Clear(ClearType::CurrentLine)).ok();
+    execute!(
+        stderr,
+        cursor::MoveToColumn(0),
+        terminal::Clear(ClearType::FromCursorDown)
+    )
+    .ok();
 
     // Build the prompt
     if no_colors {
@@ -145,7 +214,24 @@ fn show_confirmation(selector: &CommandSelector, no_colors: bool) {
             "[enter/↑/↓/ctrl+c]".dimmed()
         );
     }
+
+    // Render the preview pane beneath the prompt.
+    let mut preview_lines = 0u16;
+    let preview = selector.current_preview();
+    if !preview.is_empty() {
+        for line in preview.lines() {
+            eprint!("\r\n");
+            if no_colors {
+                eprint!("  {}", line);
+            } else {
+                eprint!("  {}", line.dimmed());
+            }
+            preview_lines += 1;
+        }
+    }
+
     stderr.flush().ok();
+    preview_lines
 }
 
 /// Shows a message when no corrections are found.
@@ -187,15 +273,21 @@ fn show_aborted(no_colors: bool) {
 
 /// Selects a command from the given corrections.
 ///
+/// `original` and `rules` are used to build each candidate's preview pane
+/// (see [`CommandSelector::with_previews`]); `rules` should be the same set
+/// passed to the [`crate::Corrector`] that produced `corrections`.
+///
 /// Returns:
 /// - The first command when confirmation is disabled
 /// - None when Ctrl+C is pressed or no corrections are available
 /// - The selected command otherwise
 pub fn select_command(
     corrections: Vec<CorrectedCommand>,
+    original: &OriginalCommand,
+    rules: &[&dyn Rule],
     settings: &Settings,
 ) -> Option<CorrectedCommand> {
-    let selector = match CommandSelector::new(corrections) {
+    let selector = match CommandSelector::with_previews(corrections, original, rules) {
         Some(s) => s,
         None => {
             show_no_match("fuck", settings.no_colors);
@@ -203,19 +295,155 @@ pub fn select_command(
         }
     };
 
-    // If confirmation is disabled, return the first command immediately
-    if !settings.require_confirmation {
+    let selected = select_command_inner(selector, rules, settings);
+
+    if settings.learning_enabled {
+        if let Some(ref cmd) = selected {
+            Learning::record_acceptance(&cmd.rule_name);
+        }
+    }
+
+    selected
+}
+
+/// Runs the actual selection flow, without recording acceptance.
+fn select_command_inner(
+    selector: CommandSelector,
+    rules: &[&dyn Rule],
+    settings: &Settings,
+) -> Option<CorrectedCommand> {
+    // If confirmation is disabled, return the first command immediately -
+    // unless it's flagged dangerous, in which case it still has to go
+    // through interactive re-confirmation rather than being auto-accepted.
+    if !settings.require_confirmation && !is_dangerous_correction(selector.current(), rules) {
         show_corrected_command(selector.current(), settings.no_colors);
         return Some(selector.commands.into_iter().next().unwrap());
     }
 
+    // If an external chooser is configured, try it first, falling back to
+    // the built-in selector if it isn't available or fails.
+    if let Some(chooser) = settings.resolve_chooser() {
+        match select_with_chooser(selector.commands(), &chooser) {
+            Some(selected) => return Some(selected),
+            None => {
+                tracing::debug!(
+                    "Chooser '{}' unavailable or aborted, falling back to interactive selector",
+                    chooser
+                );
+            }
+        }
+    }
+
     // Interactive selection
-    select_interactive(selector, settings)
+    select_interactive(selector, rules, settings)
+}
+
+/// Whether `cmd` was produced by a rule that flags it as dangerous (see
+/// [`Rule::is_dangerous`]), looking up the matching rule by name the same
+/// way [`build_preview`] does.
+fn is_dangerous_correction(cmd: &CorrectedCommand, rules: &[&dyn Rule]) -> bool {
+    rules
+        .iter()
+        .find(|rule| rule.name() == cmd.rule_name)
+        .is_some_and(|rule| rule.is_dangerous(&cmd.script))
+}
+
+/// Prompts for an explicit `y` keypress before accepting a correction
+/// flagged dangerous, rather than the single `Enter`/`Space` that accepts an
+/// ordinary one. Any other key declines and returns to the selector.
+fn confirm_dangerous(script: &str, no_colors: bool) -> bool {
+    let mut stderr = io::stderr();
+    execute!(
+        stderr,
+        cursor::MoveToColumn(0),
+        terminal::Clear(ClearType::CurrentLine)
+    )
+    .ok();
+
+    let warning = format!(
+        "'{}' looks dangerous - press 'y' to confirm, any other key to go back",
+        script
+    );
+    if no_colors {
+        eprint!("{}", warning);
+    } else {
+        eprint!("{}", warning.red().bold());
+    }
+    stderr.flush().ok();
+
+    let confirmed = loop {
+        match event::read() {
+            Ok(Event::Key(key_event)) => {
+                break matches!(key_event.code, KeyCode::Char('y') | KeyCode::Char('Y'));
+            }
+            Ok(_) => continue,
+            Err(_) => break false,
+        }
+    };
+
+    execute!(
+        io::stderr(),
+        cursor::MoveToColumn(0),
+        terminal::Clear(ClearType::CurrentLine)
+    )
+    .ok();
+
+    confirmed
+}
+
+/// Selects a command using an external fuzzy-chooser binary (e.g. `fzf`).
+///
+/// Writes each candidate's script one-per-line to the chooser's stdin and
+/// reads the single selected line back from its stdout, mapping it to the
+/// matching `CorrectedCommand`. Returns `None` if the chooser binary is
+/// missing, exits non-zero, or produces a line that doesn't match any
+/// candidate, so the caller can fall back to the built-in selector.
+fn select_with_chooser(commands: &[CorrectedCommand], chooser: &str) -> Option<CorrectedCommand> {
+    let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+
+    let mut child = create_command(&shell)
+        .arg("-c")
+        .arg(chooser)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    {
+        let stdin = child.stdin.as_mut()?;
+        let input = commands
+            .iter()
+            .map(|c| c.script.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        stdin.write_all(input.as_bytes()).ok()?;
+    }
+
+    let output = child.wait_with_output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let selected = String::from_utf8_lossy(&output.stdout);
+    let selected = selected.lines().next()?.trim();
+
+    commands.iter().find(|c| c.script == selected).cloned()
+}
+
+/// Moves the cursor back up to the start of the prompt line, undoing the
+/// `preview_lines` of preview pane rendered beneath it, so the next
+/// `show_confirmation` call can clear and redraw the whole region.
+fn move_to_prompt_start(preview_lines: u16) {
+    if preview_lines > 0 {
+        execute!(io::stderr(), cursor::MoveUp(preview_lines)).ok();
+    }
 }
 
 /// Interactive command selection with arrow keys.
 fn select_interactive(
     mut selector: CommandSelector,
+    rules: &[&dyn Rule],
     settings: &Settings,
 ) -> Option<CorrectedCommand> {
     // Force colors on before entering raw mode (raw mode can break TTY detection)
@@ -230,12 +458,19 @@ fn select_interactive(
         return Some(selector.commands.into_iter().next().unwrap());
     }
 
-    // Show initial prompt
-    show_confirmation(&selector, settings.no_colors);
+    // Show initial prompt and preview pane
+    let mut preview_lines = show_confirmation(&selector, settings.no_colors);
 
     let result = loop {
         match read_action() {
             Ok(Action::Select) => {
+                if is_dangerous_correction(selector.current(), rules)
+                    && !confirm_dangerous(&selector.current().script, settings.no_colors)
+                {
+                    move_to_prompt_start(preview_lines);
+                    preview_lines = show_confirmation(&selector, settings.no_colors);
+                    continue;
+                }
                 eprintln!(); // New line after selection
                 break Some(selector.commands.swap_remove(selector.current_index()));
             }
@@ -245,11 +480,13 @@ fn select_interactive(
             }
             Ok(Action::Previous) => {
                 selector.previous();
-                show_confirmation(&selector, settings.no_colors);
+                move_to_prompt_start(preview_lines);
+                preview_lines = show_confirmation(&selector, settings.no_colors);
             }
             Ok(Action::Next) => {
                 selector.next();
-                show_confirmation(&selector, settings.no_colors);
+                move_to_prompt_start(preview_lines);
+                preview_lines = show_confirmation(&selector, settings.no_colors);
             }
             Err(_) => {
                 // Error reading key, abort
@@ -269,6 +506,24 @@ fn select_interactive(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_select_with_chooser_picks_matching_line() {
+        let commands = vec![
+            CorrectedCommand::new("fix1", "rule1", 100),
+            CorrectedCommand::new("fix2", "rule2", 200),
+        ];
+        // "head -n2 | tail -n1" picks the second candidate off stdin.
+        let selected = select_with_chooser(&commands, "head -n2 | tail -n1").unwrap();
+        assert_eq!(selected.script, "fix2");
+    }
+
+    #[test]
+    fn test_select_with_chooser_missing_binary_returns_none() {
+        let commands = vec![CorrectedCommand::new("fix1", "rule1", 100)];
+        let selected = select_with_chooser(&commands, "nonexistent_chooser_binary_12345");
+        assert!(selected.is_none());
+    }
+
     #[test]
     fn test_command_selector_new_empty() {
         let selector = CommandSelector::new(vec![]);
@@ -320,6 +575,98 @@ mod tests {
         assert_eq!(selector.current().script, "fix1");
     }
 
+    // Test rule that explains its correction.
+    struct ExplainingRule;
+
+    impl Rule for ExplainingRule {
+        fn name(&self) -> &str {
+            "explaining"
+        }
+
+        fn matches(&self, _command: &OriginalCommand) -> bool {
+            true
+        }
+
+        fn get_new_command(&self, _command: &OriginalCommand) -> Vec<String> {
+            vec!["fixed".to_string()]
+        }
+
+        fn explain(&self, _command: &OriginalCommand) -> Option<String> {
+            Some("explains itself".to_string())
+        }
+    }
+
+    #[test]
+    fn test_with_previews_uses_rule_explanation() {
+        let original = OriginalCommand::new("broken", None);
+        let explaining = ExplainingRule;
+        let rules: Vec<&dyn Rule> = vec![&explaining];
+        let commands = vec![CorrectedCommand::new("fixed", "explaining", 100)];
+
+        let selector = CommandSelector::with_previews(commands, &original, &rules).unwrap();
+        assert_eq!(selector.current_preview(), "explains itself");
+    }
+
+    #[test]
+    fn test_with_previews_falls_back_to_diff() {
+        let original = OriginalCommand::new("gti status", None);
+        let rules: Vec<&dyn Rule> = vec![];
+        let commands = vec![CorrectedCommand::new("git status", "git_no_command", 100)];
+
+        let selector = CommandSelector::with_previews(commands, &original, &rules).unwrap();
+        assert_eq!(selector.current_preview(), "- gti status\n+ git status");
+    }
+
+    #[test]
+    fn test_new_selector_has_no_preview() {
+        let commands = vec![CorrectedCommand::new("fix1", "rule1", 100)];
+        let selector = CommandSelector::new(commands).unwrap();
+        assert_eq!(selector.current_preview(), "");
+    }
+
+    // Test rule that just uses the default `is_dangerous` heuristic.
+    struct SudoRmRule;
+
+    impl Rule for SudoRmRule {
+        fn name(&self) -> &str {
+            "sudo_rm"
+        }
+
+        fn matches(&self, _command: &OriginalCommand) -> bool {
+            true
+        }
+
+        fn get_new_command(&self, _command: &OriginalCommand) -> Vec<String> {
+            vec!["sudo rm -r *".to_string()]
+        }
+    }
+
+    #[test]
+    fn test_is_dangerous_correction_flags_wildcard_sudo_rm() {
+        let rule = SudoRmRule;
+        let rules: Vec<&dyn Rule> = vec![&rule];
+        let cmd = CorrectedCommand::new("sudo rm -r *", "sudo_rm", 100);
+
+        assert!(is_dangerous_correction(&cmd, &rules));
+    }
+
+    #[test]
+    fn test_is_dangerous_correction_false_for_ordinary_fix() {
+        let rule = SudoRmRule;
+        let rules: Vec<&dyn Rule> = vec![&rule];
+        let cmd = CorrectedCommand::new("git push", "sudo_rm", 100);
+
+        assert!(!is_dangerous_correction(&cmd, &rules));
+    }
+
+    #[test]
+    fn test_is_dangerous_correction_false_for_unknown_rule_name() {
+        let rules: Vec<&dyn Rule> = vec![];
+        let cmd = CorrectedCommand::new("sudo rm -r *", "unregistered", 100);
+
+        assert!(!is_dangerous_correction(&cmd, &rules));
+    }
+
     #[test]
     fn test_key_to_action() {
         // Arrow keys
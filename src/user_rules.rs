@@ -1,14 +1,25 @@
 //! User-defined rules support.
 //!
-//! Allows users to create custom rules using TOML configuration files.
-//! Rules are loaded from ~/.config/thefuck-rs/rules/
+//! Allows users to create custom rules using TOML, YAML, or JSON
+//! configuration files. Rules are loaded from ~/.config/thefuck-rs/rules/
 
 use crate::config::Settings;
 use crate::types::{Command, Rule, DEFAULT_PRIORITY};
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use regex::Regex;
 use serde::Deserialize;
 use std::fs;
 use std::path::PathBuf;
+use thiserror::Error;
+
+/// Error compiling a user rule's match patterns.
+#[derive(Error, Debug)]
+pub enum PatternError {
+    #[error("invalid regex pattern: {0}")]
+    Regex(#[from] regex::Error),
+    #[error("invalid glob pattern: {0}")]
+    Glob(#[from] globset::Error),
+}
 
 /// A user-defined rule loaded from a TOML file.
 #[derive(Debug, Clone, Deserialize)]
@@ -32,14 +43,30 @@ pub struct UserRule {
     #[serde(default)]
     pub match_output: Option<String>,
 
+    /// Shell-style glob to match against the command script (e.g. `git push *`)
+    #[serde(default)]
+    pub match_script_glob: Option<String>,
+
+    /// Shell-style glob to match against the command output
+    #[serde(default)]
+    pub match_output_glob: Option<String>,
+
     /// Fixed replacement command
     #[serde(default)]
     pub new_command: Option<String>,
 
-    /// Replacement pattern (with capture groups)
+    /// Replacement pattern (with capture groups). Supports `$1`/`${name}`
+    /// for `match_script` captures and `$out1`/`${out_name}` for
+    /// `match_output` captures.
     #[serde(default)]
     pub new_command_pattern: Option<String>,
 
+    /// Multiple ordered candidate templates, using the same `$1`/`$out1`
+    /// syntax as `new_command_pattern`. Takes priority over `new_command`
+    /// and `new_command_pattern` when non-empty.
+    #[serde(default)]
+    pub new_commands: Vec<String>,
+
     /// Whether the rule requires output to match
     #[serde(default = "default_true")]
     pub requires_output: bool,
@@ -49,6 +76,12 @@ pub struct UserRule {
     script_regex: Option<Regex>,
     #[serde(skip)]
     output_regex: Option<Regex>,
+
+    // Compiled globs (not deserialized)
+    #[serde(skip)]
+    script_glob: Option<GlobSet>,
+    #[serde(skip)]
+    output_glob: Option<GlobSet>,
 }
 
 fn default_true() -> bool {
@@ -60,47 +93,144 @@ fn default_priority() -> i32 {
 }
 
 impl UserRule {
-    /// Compiles the regex patterns.
-    pub fn compile_patterns(&mut self) -> Result<(), regex::Error> {
+    /// Compiles the regex and glob patterns.
+    pub fn compile_patterns(&mut self) -> Result<(), PatternError> {
         if let Some(ref pattern) = self.match_script {
             self.script_regex = Some(Regex::new(pattern)?);
         }
         if let Some(ref pattern) = self.match_output {
             self.output_regex = Some(Regex::new(pattern)?);
         }
+        if let Some(ref pattern) = self.match_script_glob {
+            self.script_glob = Some(compile_glob(pattern)?);
+        }
+        if let Some(ref pattern) = self.match_output_glob {
+            self.output_glob = Some(compile_glob(pattern)?);
+        }
         Ok(())
     }
 }
 
+/// Compiles a single shell-style glob pattern into a `GlobSet`.
+fn compile_glob(pattern: &str) -> Result<GlobSet, globset::Error> {
+    let mut builder = GlobSetBuilder::new();
+    builder.add(Glob::new(pattern)?);
+    builder.build()
+}
+
+/// Expands `$1`/`${name}` (from `script_captures`) and `$out1`/`${out_name}`
+/// (from `output_captures`) placeholders in a `new_command_pattern` or
+/// `new_commands` template. Unmatched placeholders expand to an empty string.
+fn expand_template(
+    template: &str,
+    script_captures: Option<&regex::Captures>,
+    output_captures: Option<&regex::Captures>,
+) -> String {
+    let placeholder =
+        Regex::new(r"\$(?:out(\d+)|\{out_([A-Za-z_][A-Za-z0-9_]*)\}|(\d+)|\{([A-Za-z_][A-Za-z0-9_]*)\})")
+            .unwrap();
+
+    placeholder
+        .replace_all(template, |caps: &regex::Captures| -> String {
+            if let Some(idx) = caps.get(1) {
+                capture_at(output_captures, CaptureRef::Index(idx.as_str()))
+            } else if let Some(name) = caps.get(2) {
+                capture_at(output_captures, CaptureRef::Name(name.as_str()))
+            } else if let Some(idx) = caps.get(3) {
+                capture_at(script_captures, CaptureRef::Index(idx.as_str()))
+            } else if let Some(name) = caps.get(4) {
+                capture_at(script_captures, CaptureRef::Name(name.as_str()))
+            } else {
+                String::new()
+            }
+        })
+        .to_string()
+}
+
+/// Which capture a template placeholder refers to.
+enum CaptureRef<'a> {
+    Index(&'a str),
+    Name(&'a str),
+}
+
+fn capture_at(captures: Option<&regex::Captures>, reference: CaptureRef) -> String {
+    let captures = match captures {
+        Some(c) => c,
+        None => return String::new(),
+    };
+
+    let matched = match reference {
+        CaptureRef::Index(idx) => idx.parse().ok().and_then(|i| captures.get(i)),
+        CaptureRef::Name(name) => captures.name(name),
+    };
+
+    matched.map(|m| m.as_str().to_string()).unwrap_or_default()
+}
+
 impl Rule for UserRule {
     fn name(&self) -> &str {
         &self.name
     }
 
     fn matches(&self, command: &Command) -> bool {
-        // Check script pattern
+        // Check script regex
         if let Some(ref regex) = self.script_regex {
             if !regex.is_match(&command.script) {
                 return false;
             }
         }
 
-        // Check output pattern
-        if let Some(ref regex) = self.output_regex {
-            if let Some(ref output) = command.output {
-                if !regex.is_match(output) {
-                    return false;
-                }
-            } else {
+        // Check script glob
+        if let Some(ref glob) = self.script_glob {
+            if !glob.is_match(&command.script) {
                 return false;
             }
         }
 
+        // Check output regex
+        if let Some(ref regex) = self.output_regex {
+            match &command.output {
+                Some(output) if regex.is_match(output) => {}
+                _ => return false,
+            }
+        }
+
+        // Check output glob
+        if let Some(ref glob) = self.output_glob {
+            match &command.output {
+                Some(output) if glob.is_match(output.as_str()) => {}
+                _ => return false,
+            }
+        }
+
         // At least one pattern must be defined
-        self.script_regex.is_some() || self.output_regex.is_some()
+        self.script_regex.is_some()
+            || self.output_regex.is_some()
+            || self.script_glob.is_some()
+            || self.output_glob.is_some()
     }
 
     fn get_new_command(&self, command: &Command) -> Vec<String> {
+        let script_captures = self
+            .script_regex
+            .as_ref()
+            .and_then(|re| re.captures(&command.script));
+        let output_captures = self.output_regex.as_ref().and_then(|re| {
+            command
+                .output
+                .as_ref()
+                .and_then(|output| re.captures(output))
+        });
+
+        // Multiple ordered candidates take priority over the single-result fields.
+        if !self.new_commands.is_empty() {
+            return self
+                .new_commands
+                .iter()
+                .map(|template| expand_template(template, script_captures.as_ref(), output_captures.as_ref()))
+                .collect();
+        }
+
         // Fixed replacement
         if let Some(ref new_cmd) = self.new_command {
             return vec![new_cmd.clone()];
@@ -108,10 +238,11 @@ impl Rule for UserRule {
 
         // Pattern-based replacement
         if let Some(ref pattern) = self.new_command_pattern {
-            if let Some(ref regex) = self.script_regex {
-                let result = regex.replace(&command.script, pattern.as_str());
-                return vec![result.to_string()];
-            }
+            return vec![expand_template(
+                pattern,
+                script_captures.as_ref(),
+                output_captures.as_ref(),
+            )];
         }
 
         vec![]
@@ -151,8 +282,15 @@ pub fn load_user_rules() -> Vec<Box<dyn Rule>> {
     for entry in entries.flatten() {
         let path = entry.path();
 
-        if path.extension().is_some_and(|ext| ext == "toml") {
-            if let Some(rule) = load_rule_from_file(&path) {
+        let format = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Some(RuleFileFormat::Toml),
+            Some("yaml") | Some("yml") => Some(RuleFileFormat::Yaml),
+            Some("json") => Some(RuleFileFormat::Json),
+            _ => None,
+        };
+
+        if let Some(format) = format {
+            if let Some(rule) = load_rule_from_file(&path, format) {
                 rules.push(Box::new(rule));
             }
         }
@@ -162,11 +300,26 @@ pub fn load_user_rules() -> Vec<Box<dyn Rule>> {
     rules
 }
 
-/// Loads a single rule from a TOML file.
-fn load_rule_from_file(path: &PathBuf) -> Option<UserRule> {
+/// The file formats a user rule can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RuleFileFormat {
+    Toml,
+    Yaml,
+    Json,
+}
+
+/// Loads a single rule from a TOML, YAML, or JSON file, keyed off its
+/// extension.
+fn load_rule_from_file(path: &PathBuf, format: RuleFileFormat) -> Option<UserRule> {
     let content = fs::read_to_string(path).ok()?;
 
-    let mut rule: UserRule = match toml::from_str(&content) {
+    let parsed = match format {
+        RuleFileFormat::Toml => toml::from_str(&content).map_err(|e| e.to_string()),
+        RuleFileFormat::Yaml => serde_yaml::from_str(&content).map_err(|e| e.to_string()),
+        RuleFileFormat::Json => serde_json::from_str(&content).map_err(|e| e.to_string()),
+    };
+
+    let mut rule: UserRule = match parsed {
         Ok(r) => r,
         Err(e) => {
             tracing::warn!("Failed to parse rule file {:?}: {}", path, e);
@@ -209,12 +362,24 @@ match_script = "^mycommand (.+)$"
 # Match pattern for the command output (regex, optional)
 # match_output = "error: (.+)"
 
+# Or match the script/output with a shell-style glob instead of regex.
+# Supports *, ?, [...] and {a,b} alternation. A rule may mix a glob for one
+# field with a regex for the other, but capture-group placeholders below
+# only work against a regex field (match_script/match_output), not a glob.
+# match_script_glob = "git push *"
+# match_output_glob = "*Is a directory*"
+
 # Fixed replacement command
 # new_command = "mycommand --fixed"
 
-# Or use pattern-based replacement with capture groups
+# Or use pattern-based replacement: $1/${name} reference match_script's
+# capture groups, $out1/${out_name} reference match_output's.
 new_command_pattern = "mycommand --correct $1"
 
+# Or propose several ordered candidates using the same $1/$out1 syntax.
+# Takes priority over new_command/new_command_pattern when non-empty.
+# new_commands = ["mycommand --correct $1", "mycommand --force $1"]
+
 # Whether this rule requires command output to match
 requires_output = false
 "#;
@@ -236,11 +401,16 @@ mod tests {
             priority: 1000,
             match_script: Some("^git psuh".to_string()),
             match_output: None,
+            match_script_glob: None,
+            match_output_glob: None,
             new_command: Some("git push".to_string()),
             new_command_pattern: None,
+            new_commands: vec![],
             requires_output: false,
             script_regex: None,
             output_regex: None,
+            script_glob: None,
+            output_glob: None,
         };
         rule.compile_patterns().unwrap();
 
@@ -256,11 +426,16 @@ mod tests {
             priority: 1000,
             match_script: Some("^git psuh (.+)$".to_string()),
             match_output: None,
+            match_script_glob: None,
+            match_output_glob: None,
             new_command: None,
             new_command_pattern: Some("git push $1".to_string()),
+            new_commands: vec![],
             requires_output: false,
             script_regex: None,
             output_regex: None,
+            script_glob: None,
+            output_glob: None,
         };
         rule.compile_patterns().unwrap();
 
@@ -277,11 +452,16 @@ mod tests {
             priority: 1000,
             match_script: Some("^cat ".to_string()),
             match_output: Some("Is a directory".to_string()),
+            match_script_glob: None,
+            match_output_glob: None,
             new_command: Some("ls".to_string()),
             new_command_pattern: None,
+            new_commands: vec![],
             requires_output: true,
             script_regex: None,
             output_regex: None,
+            script_glob: None,
+            output_glob: None,
         };
         rule.compile_patterns().unwrap();
 
@@ -292,4 +472,177 @@ mod tests {
         let cmd_without_output = Command::new("cat /tmp", None);
         assert!(!rule.matches(&cmd_without_output));
     }
+
+    #[test]
+    fn test_user_rule_matches_script_glob() {
+        let mut rule = UserRule {
+            name: "test".to_string(),
+            enabled: true,
+            priority: 1000,
+            match_script: None,
+            match_output: None,
+            match_script_glob: Some("git push *".to_string()),
+            match_output_glob: None,
+            new_command: Some("git push --force".to_string()),
+            new_command_pattern: None,
+            new_commands: vec![],
+            requires_output: false,
+            script_regex: None,
+            output_regex: None,
+            script_glob: None,
+            output_glob: None,
+        };
+        rule.compile_patterns().unwrap();
+
+        assert!(rule.matches(&Command::new("git push origin main", None)));
+        assert!(!rule.matches(&Command::new("git pull origin main", None)));
+    }
+
+    #[test]
+    fn test_user_rule_mixes_script_glob_with_output_regex() {
+        let mut rule = UserRule {
+            name: "test".to_string(),
+            enabled: true,
+            priority: 1000,
+            match_script: None,
+            match_output: Some("Is a directory".to_string()),
+            match_script_glob: Some("cat *".to_string()),
+            match_output_glob: None,
+            new_command: Some("ls".to_string()),
+            new_command_pattern: None,
+            new_commands: vec![],
+            requires_output: true,
+            script_regex: None,
+            output_regex: None,
+            script_glob: None,
+            output_glob: None,
+        };
+        rule.compile_patterns().unwrap();
+
+        let matching = Command::new("cat /tmp", Some("cat: /tmp: Is a directory".to_string()));
+        assert!(rule.matches(&matching));
+
+        let wrong_output = Command::new("cat /tmp", Some("no such file".to_string()));
+        assert!(!rule.matches(&wrong_output));
+    }
+
+    #[test]
+    fn test_user_rule_invalid_glob_fails_to_compile() {
+        let mut rule = UserRule {
+            name: "test".to_string(),
+            enabled: true,
+            priority: 1000,
+            match_script: None,
+            match_output: None,
+            match_script_glob: Some("[unterminated".to_string()),
+            match_output_glob: None,
+            new_command: Some("ls".to_string()),
+            new_command_pattern: None,
+            new_commands: vec![],
+            requires_output: false,
+            script_regex: None,
+            output_regex: None,
+            script_glob: None,
+            output_glob: None,
+        };
+
+        assert!(rule.compile_patterns().is_err());
+    }
+
+    #[test]
+    fn test_user_rule_pattern_references_output_capture() {
+        let mut rule = UserRule {
+            name: "test".to_string(),
+            enabled: true,
+            priority: 1000,
+            match_script: Some("^git push(.*)$".to_string()),
+            match_output: Some(r"Did you mean '(\S+)'\?".to_string()),
+            match_script_glob: None,
+            match_output_glob: None,
+            new_command: None,
+            new_command_pattern: Some("git push$1 --set-upstream origin $out1".to_string()),
+            new_commands: vec![],
+            requires_output: true,
+            script_regex: None,
+            output_regex: None,
+            script_glob: None,
+            output_glob: None,
+        };
+        rule.compile_patterns().unwrap();
+
+        let cmd = Command::new(
+            "git push",
+            Some("Did you mean 'feature/foo'?".to_string()),
+        );
+        let result = rule.get_new_command(&cmd);
+        assert_eq!(result, vec!["git push --set-upstream origin feature/foo"]);
+    }
+
+    #[test]
+    fn test_user_rule_new_commands_emits_multiple_candidates() {
+        let mut rule = UserRule {
+            name: "test".to_string(),
+            enabled: true,
+            priority: 1000,
+            match_script: Some("^git psuh (.+)$".to_string()),
+            match_output: None,
+            match_script_glob: None,
+            match_output_glob: None,
+            new_command: None,
+            new_command_pattern: None,
+            new_commands: vec!["git push $1".to_string(), "git push --force $1".to_string()],
+            requires_output: false,
+            script_regex: None,
+            output_regex: None,
+            script_glob: None,
+            output_glob: None,
+        };
+        rule.compile_patterns().unwrap();
+
+        let cmd = Command::new("git psuh origin main", None);
+        let result = rule.get_new_command(&cmd);
+        assert_eq!(
+            result,
+            vec!["git push origin main", "git push --force origin main"]
+        );
+    }
+
+    #[test]
+    fn test_load_rule_from_file_yaml() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mycommand.yaml");
+        fs::write(
+            &path,
+            "name: mycommand\nmatch_script: \"^mycommand (.+)$\"\nnew_command_pattern: \"mycommand --correct $1\"\nrequires_output: false\n",
+        )
+        .unwrap();
+
+        let rule = load_rule_from_file(&path, RuleFileFormat::Yaml).unwrap();
+        let cmd = Command::new("mycommand foo", None);
+        assert_eq!(rule.get_new_command(&cmd), vec!["mycommand --correct foo"]);
+    }
+
+    #[test]
+    fn test_load_rule_from_file_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mycommand.json");
+        fs::write(
+            &path,
+            r#"{"name": "mycommand", "match_script": "^mycommand (.+)$", "new_command_pattern": "mycommand --correct $1", "requires_output": false}"#,
+        )
+        .unwrap();
+
+        let rule = load_rule_from_file(&path, RuleFileFormat::Json).unwrap();
+        let cmd = Command::new("mycommand foo", None);
+        assert_eq!(rule.get_new_command(&cmd), vec!["mycommand --correct foo"]);
+    }
+
+    #[test]
+    fn test_load_rule_from_file_rejects_invalid_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("broken.json");
+        fs::write(&path, "{ not valid json").unwrap();
+
+        assert!(load_rule_from_file(&path, RuleFileFormat::Json).is_none());
+    }
 }